@@ -0,0 +1,50 @@
+//! Conversions between little-endian values and `num_bigint::BigUint`.
+
+use num_bigint::BigUint;
+
+
+/// Decode a little-endian value. An empty value is zero.
+pub fn value_to_biguint(value: &[u8]) -> BigUint {
+    BigUint::from_bytes_le(value)
+}
+
+/// Encode a value in little-endian, padded with zeros to at least `byte_len` bytes.
+/// See `field_byte_len` for the width of the elements of a field.
+pub fn biguint_to_value(n: &BigUint, byte_len: usize) -> Vec<u8> {
+    let mut value = n.to_bytes_le();
+    if value == [0] && byte_len == 0 {
+        value.clear();
+    }
+    if value.len() < byte_len {
+        value.resize(byte_len, 0);
+    }
+    value
+}
+
+/// The number of bytes needed to encode any element of the field of `field_maximum`.
+pub fn field_byte_len(field_maximum: &[u8]) -> usize {
+    let bits = value_to_biguint(field_maximum).bits() as usize;
+    bits.div_ceil(8).max(1)
+}
+
+
+#[test]
+fn test_biguint_conversions() {
+    // The largest element of the BN254 scalar field, of 254 bits.
+    let maximum: BigUint = "21888242871839275222246405745257275088548364400416034343698204186575808495616"
+        .parse().unwrap();
+    assert_eq!(maximum.bits(), 254);
+
+    let value = biguint_to_value(&maximum, 32);
+    assert_eq!(value.len(), 32);
+    assert_eq!(value_to_biguint(&value), maximum);
+    assert_eq!(field_byte_len(&value), 32);
+
+    // Padding, and values longer than requested.
+    assert_eq!(biguint_to_value(&BigUint::from(258u32), 4), vec![2, 1, 0, 0]);
+    assert_eq!(biguint_to_value(&BigUint::from(258u32), 1), vec![2, 1]);
+    assert_eq!(biguint_to_value(&BigUint::from(0u32), 0), Vec::<u8>::new());
+    assert_eq!(value_to_biguint(&[]), BigUint::from(0u32));
+    assert_eq!(value_to_biguint(&[7, 0, 0]), BigUint::from(7u32));
+    assert_eq!(field_byte_len(&[100]), 1);
+}
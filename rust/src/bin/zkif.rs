@@ -31,6 +31,11 @@ Print a statement in different forms:
     cargo run explain local
     cargo run stats   local
 
+Convert a statement from JSON back to the binary format (JSON from the `json` or `pretty` commands):
+    cargo run from_json local/example.json > local/example.zkif
+Or:
+    cargo run from_json - < local/example.json > local/example.zkif
+
 Simulate a proving system:
     cargo run fake_prove  local
     cargo run fake_verify local
@@ -58,6 +63,7 @@ pub fn main() -> Result<()> {
         "pretty" => main_pretty(&load_messages(paths)?),
         "explain" => main_explain(&load_messages(paths)?),
         "stats" => main_stats(&load_messages(paths)?),
+        "from_json" => main_from_json(paths),
         "fake_prove" => main_fake_prove(&load_messages(paths)?),
         "fake_verify" => main_fake_verify(&load_messages(paths)?),
         _ => {
@@ -163,6 +169,17 @@ pub fn main_pretty(messages: &Messages) -> Result<()> {
     Ok(())
 }
 
+pub fn main_from_json(args: &[&str]) -> Result<()> {
+    let mut json = String::new();
+    if args.is_empty() || args[0] == "-" {
+        stdin().read_to_string(&mut json)?;
+    } else {
+        File::open(args[0])?.read_to_string(&mut json)?;
+    }
+    let messages_owned = MessagesOwned::from_json(&json)?;
+    messages_owned.write_into(&mut stdout())
+}
+
 pub fn main_explain(messages: &Messages) -> Result<()> {
     eprintln!("{:?}", messages);
     Ok(())
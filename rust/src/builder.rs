@@ -0,0 +1,194 @@
+//! Build a statement in Rust, with variable IDs allocated automatically.
+
+use std::collections::BTreeMap;
+
+use crate::{CircuitOwned, ConstraintOwned, ConstraintSystemOwned, KeyValueOwned, VariablesOwned, WitnessOwned};
+use crate::owned::constraints::LinComb;
+use crate::statement::VariableManager;
+use crate::field::Uint;
+use crate::format::VARIABLE_NAMES_KEY;
+
+/// Signed coefficients for `CircuitBuilder::constrain`.
+pub use crate::field::from_i64;
+
+
+/// Allocates variables and collects constraints and values.
+///
+/// IDs are allocated in increasing order starting at 1. The ID 0 is the constant one.
+pub struct CircuitBuilder {
+    vars: VariableManager,
+    field_maximum: Option<Vec<u8>>,
+    instance: Vec<(u64, Vec<u8>)>,
+    witness: Vec<(u64, Option<Vec<u8>>)>,
+    constraints: Vec<ConstraintOwned>,
+    names: BTreeMap<u64, String>,
+}
+
+impl CircuitBuilder {
+    pub fn new(field_maximum: Option<Vec<u8>>) -> CircuitBuilder {
+        CircuitBuilder {
+            vars: VariableManager::new(),
+            field_maximum,
+            instance: vec![],
+            witness: vec![],
+            constraints: vec![],
+            names: BTreeMap::new(),
+        }
+    }
+
+    /// Allocate an instance variable, which becomes a connection of the circuit.
+    pub fn alloc_instance(&mut self, value: Vec<u8>) -> u64 {
+        let id = self.vars.allocate();
+        self.instance.push((id, value));
+        id
+    }
+
+    /// Allocate a witness variable, without value. See `assign`.
+    pub fn alloc_witness(&mut self) -> u64 {
+        let id = self.vars.allocate();
+        self.witness.push((id, None));
+        id
+    }
+
+    /// Allocate a witness variable with a name, and assign it if a value is given.
+    /// See `set_name`.
+    pub fn alloc_named(&mut self, name: &str, value: Option<Vec<u8>>) -> u64 {
+        let id = self.alloc_witness();
+        if let Some(value) = value {
+            self.assign(id, value);
+        }
+        self.set_name(id, name);
+        id
+    }
+
+    /// Name a variable. The names are written in the configuration of the circuit under the key
+    /// `VARIABLE_NAMES_KEY`, and used by `format::format_constraints`.
+    pub fn set_name(&mut self, id: u64, name: &str) {
+        self.names.insert(id, name.to_string());
+    }
+
+    /// Set the value of a witness variable.
+    ///
+    /// # Panics
+    /// If `id` was not allocated with `alloc_witness`.
+    pub fn assign(&mut self, id: u64, value: Vec<u8>) {
+        let i = self.witness.binary_search_by_key(&id, |(id, _)| *id)
+            .unwrap_or_else(|_| panic!("variable {} is not a witness variable", id));
+        self.witness[i].1 = Some(value);
+    }
+
+    /// Encode a signed coefficient in the field of the circuit, see `from_i64`.
+    ///
+    /// # Panics
+    /// If the builder has no `field_maximum`.
+    pub fn signed(&self, value: i64) -> Vec<u8> {
+        let field_maximum = self.field_maximum.as_ref()
+            .expect("signed coefficients require a field_maximum");
+        let modulus = Uint::from_le_bytes(field_maximum).add(&Uint::one());
+        from_i64(value, &modulus.to_le_bytes(0))
+    }
+
+    /// Add the constraint `a * b = c`. Use `signed` for negative coefficients.
+    pub fn constrain(&mut self, a: LinComb, b: LinComb, c: LinComb) {
+        self.constraints.push(ConstraintOwned { a, b, c });
+    }
+
+    /// Produce the statement.
+    /// The witness has values only if all witness variables were assigned.
+    pub fn finish(self) -> (CircuitOwned, ConstraintSystemOwned, WitnessOwned) {
+        let circuit = CircuitOwned {
+            connections: VariablesOwned::from(&self.instance),
+            free_variable_id: self.vars.free_variable_id,
+            field_maximum: self.field_maximum,
+            configuration: if self.names.is_empty() {
+                None
+            } else {
+                Some(vec![KeyValueOwned {
+                    key: VARIABLE_NAMES_KEY.to_string(),
+                    text: Some(serde_json::to_string(&self.names).unwrap()),
+                    data: None,
+                    number: 0,
+                }])
+            },
+        };
+
+        let values: Option<LinComb> = self.witness.iter()
+            .map(|(id, value)| value.clone().map(|value| (*id, value)))
+            .collect();
+        let assigned_variables = match values {
+            Some(values) => VariablesOwned::from(&values),
+            None => VariablesOwned {
+                variable_ids: self.witness.iter().map(|(id, _)| *id).collect(),
+                values: None,
+            },
+        };
+
+        let cs = self.constraints.into_iter().collect();
+        (circuit, cs, WitnessOwned { assigned_variables })
+    }
+}
+
+
+#[test]
+fn test_circuit_builder() {
+    use crate::satisfaction::verify_satisfaction;
+
+    let mut builder = CircuitBuilder::new(Some(vec![100]));
+    let c = builder.alloc_instance(vec![42]);
+    let a = builder.alloc_witness();
+    let b = builder.alloc_witness();
+    builder.assign(a, vec![6]);
+    builder.assign(b, vec![7]);
+    builder.constrain(vec![(a, vec![1])], vec![(b, vec![1])], vec![(c, vec![1])]);
+    let (circuit, cs, witness) = builder.finish();
+
+    assert_eq!((c, a, b), (1, 2, 3));
+    assert_eq!(circuit.connections.variable_ids, vec![1]);
+    assert_eq!(circuit.free_variable_id, 4);
+    assert_eq!(witness.assigned_variables.variable_ids, vec![2, 3]);
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+
+    // 1 * (c - 42) = 0, with a signed coefficient.
+    let mut builder = CircuitBuilder::new(Some(vec![100]));
+    let c = builder.alloc_instance(vec![42]);
+    assert_eq!(builder.signed(-42), vec![59]);
+    let minus_42 = builder.signed(-42);
+    builder.constrain(vec![(0, vec![1])], vec![(c, vec![1]), (0, minus_42)], vec![]);
+    let (circuit, cs, witness) = builder.finish();
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+
+    // Without values for the witness.
+    let mut builder = CircuitBuilder::new(None);
+    builder.alloc_instance(vec![42]);
+    let a = builder.alloc_witness();
+    builder.alloc_witness();
+    builder.assign(a, vec![6]);
+    let (_, _, witness) = builder.finish();
+    assert_eq!(witness.assigned_variables, VariablesOwned { variable_ids: vec![2, 3], values: None });
+}
+
+#[test]
+fn test_alloc_named() {
+    use crate::{MessageOwned, MessageReader};
+    use crate::format::format_constraints;
+
+    let mut builder = CircuitBuilder::new(Some(vec![100]));
+    let c = builder.alloc_instance(vec![42]);
+    builder.set_name(c, "product");
+    let a = builder.alloc_named("left", Some(vec![6]));
+    let b = builder.alloc_named("right", None);
+    builder.assign(b, vec![7]);
+    builder.constrain(vec![(a, vec![1])], vec![(b, vec![1])], vec![(c, vec![1])]);
+    let (circuit, cs, witness) = builder.finish();
+    assert_eq!(witness.assigned_variables.values, Some(vec![6, 7]));
+    assert_eq!(circuit.get_config_string(VARIABLE_NAMES_KEY),
+               Some(r#"{"1":"product","2":"left","3":"right"}"#));
+
+    let mut buf = Vec::<u8>::new();
+    circuit.write_into(&mut buf).unwrap();
+    let circuit = match MessageReader::new(&buf[..]).next_message().unwrap() {
+        Some(MessageOwned::Circuit(circuit)) => circuit,
+        _ => panic!("expected a circuit"),
+    };
+    assert_eq!(format_constraints(&circuit, &cs), "(left) * (right) = (product)\n");
+}
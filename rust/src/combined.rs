@@ -0,0 +1,130 @@
+//! Self-contained files holding a circuit, its constraints, and a witness.
+
+use std::io::{Read, Write};
+
+use crate::{Result, CircuitOwned, ConstraintSystemOwned, MessageOwned, MessageReader, WitnessOwned};
+use crate::statement::Statement;
+
+
+/// Write the three messages of a statement, in the order circuit, constraints, witness.
+pub fn write_combined(circuit: &CircuitOwned, cs: &ConstraintSystemOwned, witness: &WitnessOwned, writer: &mut impl Write) -> Result<()> {
+    circuit.write_into(writer)?;
+    cs.write_into(writer)?;
+    witness.write_into(writer)
+}
+
+/// Read the three messages of a statement, in any order.
+/// Each message type must be present exactly once. Command messages are ignored.
+pub fn read_combined(reader: &mut impl Read) -> Result<(CircuitOwned, ConstraintSystemOwned, WitnessOwned)> {
+    let mut circuit = None;
+    let mut cs = None;
+    let mut witness = None;
+
+    fn set<T>(slot: &mut Option<T>, value: T, name: &str) -> Result<()> {
+        if slot.is_some() {
+            return Err(format!("more than one {} message", name).into());
+        }
+        *slot = Some(value);
+        Ok(())
+    }
+
+    let mut reader = MessageReader::new(reader);
+    while let Some(message) = reader.next_message()? {
+        match message {
+            MessageOwned::Circuit(msg) => set(&mut circuit, msg, "Circuit")?,
+            MessageOwned::ConstraintSystem(msg) => set(&mut cs, msg, "ConstraintSystem")?,
+            MessageOwned::Witness(msg) => set(&mut witness, msg, "Witness")?,
+            MessageOwned::Command(_) => {}
+        }
+    }
+
+    Ok((
+        circuit.ok_or("missing Circuit message")?,
+        cs.ok_or("missing ConstraintSystem message")?,
+        witness.ok_or("missing Witness message")?,
+    ))
+}
+
+/// Read a circuit with its constraints in any number of ConstraintSystem messages, in order.
+/// The Circuit message must be present exactly once. Witness and Command messages are ignored.
+pub fn read_statement(reader: &mut impl Read) -> Result<Statement> {
+    let mut circuit = None;
+    let mut constraint_systems = vec![];
+
+    let mut reader = MessageReader::new(reader);
+    while let Some(message) = reader.next_message()? {
+        match message {
+            MessageOwned::Circuit(msg) => {
+                if circuit.is_some() {
+                    return Err("more than one Circuit message".into());
+                }
+                circuit = Some(msg);
+            }
+            MessageOwned::ConstraintSystem(msg) => constraint_systems.push(msg),
+            MessageOwned::Witness(_) | MessageOwned::Command(_) => {}
+        }
+    }
+
+    Ok(Statement {
+        circuit: circuit.ok_or("missing Circuit message")?,
+        constraint_systems,
+    })
+}
+
+
+#[test]
+fn test_combined() {
+    use crate::examples::*;
+
+    let (circuit, cs, witness) = (example_circuit(), example_constraints(), example_witness());
+
+    let mut buf = Vec::<u8>::new();
+    write_combined(&circuit, &cs, &witness, &mut buf).unwrap();
+    let read = read_combined(&mut &buf[..]).unwrap();
+    assert_eq!(read, (circuit.clone(), cs.clone(), witness.clone()));
+
+    // Any order.
+    let mut buf = Vec::<u8>::new();
+    witness.write_into(&mut buf).unwrap();
+    circuit.write_into(&mut buf).unwrap();
+    cs.write_into(&mut buf).unwrap();
+    let read = read_combined(&mut &buf[..]).unwrap();
+    assert_eq!(read, (circuit.clone(), cs.clone(), witness.clone()));
+
+    // Missing witness.
+    let mut buf = Vec::<u8>::new();
+    circuit.write_into(&mut buf).unwrap();
+    cs.write_into(&mut buf).unwrap();
+    let err = read_combined(&mut &buf[..]).unwrap_err();
+    assert_eq!(err.to_string(), "missing Witness message");
+
+    // Duplicate circuit.
+    circuit.write_into(&mut buf).unwrap();
+    let err = read_combined(&mut &buf[..]).unwrap_err();
+    assert_eq!(err.to_string(), "more than one Circuit message");
+}
+
+#[test]
+fn test_read_statement() {
+    use crate::examples::*;
+
+    let cs = example_constraints();
+    let chunks = [
+        ConstraintSystemOwned { constraints: cs.constraints[..1].to_vec() },
+        ConstraintSystemOwned { constraints: cs.constraints[1..].to_vec() },
+    ];
+    let mut buf = Vec::<u8>::new();
+    chunks[0].write_into(&mut buf).unwrap();
+    example_circuit().write_into(&mut buf).unwrap();
+    example_witness().write_into(&mut buf).unwrap();
+    chunks[1].write_into(&mut buf).unwrap();
+
+    let statement = read_statement(&mut &buf[..]).unwrap();
+    assert_eq!(statement.circuit, example_circuit());
+    assert_eq!(statement.constraint_systems, chunks.to_vec());
+    statement.validate().unwrap();
+    assert_eq!(statement.all_constraints().count(), 3);
+
+    let err = read_statement(&mut &[][..]).unwrap_err();
+    assert_eq!(err.to_string(), "missing Circuit message");
+}
@@ -0,0 +1,83 @@
+//! Gzip-compressed message files.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::{Messages, Result};
+
+
+/// The first bytes of a gzip stream: magic number and the deflate method.
+const GZIP_HEADER: [u8; 3] = [0x1f, 0x8b, 0x08];
+
+/// Read a file of messages, gzip-compressed or not.
+///
+/// A file starting with the gzip header is decompressed. A valid uncompressed file may start with
+/// the same bytes, if its first message has a matching size; if it does not decompress, it is
+/// read as is.
+pub fn read_compressed(path: &Path) -> Result<Messages> {
+    let buf = fs::read(path)?;
+
+    let mut messages = Messages::new();
+    if buf.starts_with(&GZIP_HEADER) {
+        let mut decompressed = vec![];
+        if GzDecoder::new(&buf[..]).read_to_end(&mut decompressed).is_ok() {
+            messages.push_message(decompressed)?;
+            return Ok(messages);
+        }
+    }
+    messages.push_message(buf)?;
+    Ok(messages)
+}
+
+/// Write messages into a gzip-compressed file.
+pub fn write_compressed(path: &Path, messages: &Messages) -> Result<()> {
+    let mut encoder = GzEncoder::new(BufWriter::new(File::create(path)?), Compression::default());
+    for buf in &messages.messages {
+        encoder.write_all(buf)?;
+    }
+    encoder.finish()?.flush()?;
+    Ok(())
+}
+
+
+#[test]
+fn test_compression() {
+    use crate::examples::*;
+    use crate::MessagesOwned;
+
+    let dir = std::env::temp_dir().join(format!("zkinterface_test_compression_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let compressed_path = dir.join("statement.zkif.gz");
+    let plain_path = dir.join("statement.zkif");
+
+    let mut buf = Vec::<u8>::new();
+    example_circuit().write_into(&mut buf).unwrap();
+    example_constraints().write_into(&mut buf).unwrap();
+    example_witness().write_into(&mut buf).unwrap();
+    let mut messages = Messages::new();
+    messages.push_message(buf.clone()).unwrap();
+
+    write_compressed(&compressed_path, &messages).unwrap();
+    fs::write(&plain_path, &buf).unwrap();
+    let compressed = fs::read(&compressed_path).unwrap();
+    let from_compressed = read_compressed(&compressed_path).unwrap();
+    let from_plain = read_compressed(&plain_path).unwrap();
+
+    // An uncompressed file starting like a gzip stream: read as is, and verified.
+    let mut lookalike = buf.clone();
+    lookalike[..3].copy_from_slice(&GZIP_HEADER);
+    fs::write(&plain_path, &lookalike).unwrap();
+    let lookalike_err = read_compressed(&plain_path).unwrap_err();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(compressed.starts_with(&GZIP_HEADER));
+    assert_ne!(compressed, buf);
+    assert_eq!(MessagesOwned::from(&from_compressed), MessagesOwned::from(&messages));
+    assert_eq!(MessagesOwned::from(&from_plain), MessagesOwned::from(&messages));
+    assert!(lookalike_err.to_string().starts_with("truncated message"));
+}
@@ -0,0 +1,126 @@
+//! Compare constraint systems.
+
+use std::collections::{HashMap, VecDeque};
+
+use crate::{ConstraintOwned, ConstraintSystemOwned};
+
+
+/// A difference between two constraint systems, see `diff`.
+/// Indices are positions of constraints in their system.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ConstraintDiff {
+    /// A constraint of the first system has no counterpart in the second.
+    OnlyInFirst { index: usize, constraint: ConstraintOwned },
+    /// A constraint of the second system has no counterpart in the first.
+    OnlyInSecond { index: usize, constraint: ConstraintOwned },
+    /// Two constraints use the same variables in the same places, with different coefficients.
+    CoefficientsDiffer { index_a: usize, index_b: usize, a: ConstraintOwned, b: ConstraintOwned },
+}
+
+/// Compare two constraint systems in the field of `field_maximum`, regardless of the order of
+/// the constraints. Returns nothing if the systems are equivalent.
+///
+/// Both systems are canonicalized first (see `ConstraintOwned::canonicalize`), so coefficients
+/// are compared modulo the field, and the order and repetition of terms do not matter.
+/// Identical constraints are matched first, then the remaining constraints with the same
+/// variables, both in order of appearance.
+///
+/// The result is ordered: first the differences of the constraints of `a` by index, either
+/// `CoefficientsDiffer` or `OnlyInFirst`, then the `OnlyInSecond` of `b` by index.
+/// The constraints in the result are canonical.
+pub fn diff(a: &ConstraintSystemOwned, b: &ConstraintSystemOwned, field_maximum: &[u8]) -> Vec<ConstraintDiff> {
+    let canonical = |cs: &ConstraintSystemOwned| -> Vec<ConstraintOwned> {
+        cs.iter_constraints().map(|mut constraint| {
+            constraint.canonicalize(field_maximum);
+            constraint
+        }).collect()
+    };
+    let (a, b) = (canonical(a), canonical(b));
+    let mut matched_a = vec![false; a.len()];
+    let mut matched_b = vec![false; b.len()];
+
+    // Identical constraints.
+    let mut by_constraint = HashMap::<&ConstraintOwned, VecDeque<usize>>::new();
+    for (j, constraint) in b.iter().enumerate() {
+        by_constraint.entry(constraint).or_default().push_back(j);
+    }
+    for (i, constraint) in a.iter().enumerate() {
+        if let Some(j) = by_constraint.get_mut(constraint).and_then(|js| js.pop_front()) {
+            matched_a[i] = true;
+            matched_b[j] = true;
+        }
+    }
+
+    // Constraints with the same variables.
+    let shape = |constraint: &ConstraintOwned| {
+        let ids = |lc: &[(u64, Vec<u8>)]| lc.iter().map(|(id, _)| *id).collect::<Vec<u64>>();
+        (ids(&constraint.a), ids(&constraint.b), ids(&constraint.c))
+    };
+    let mut by_shape = HashMap::<_, VecDeque<usize>>::new();
+    for (j, constraint) in b.iter().enumerate() {
+        if !matched_b[j] {
+            by_shape.entry(shape(constraint)).or_default().push_back(j);
+        }
+    }
+
+    let mut diffs = vec![];
+    for (i, constraint) in a.iter().enumerate() {
+        if matched_a[i] { continue; }
+        match by_shape.get_mut(&shape(constraint)).and_then(|js| js.pop_front()) {
+            Some(j) => {
+                matched_b[j] = true;
+                diffs.push(ConstraintDiff::CoefficientsDiffer {
+                    index_a: i,
+                    index_b: j,
+                    a: constraint.clone(),
+                    b: b[j].clone(),
+                });
+            }
+            None => diffs.push(ConstraintDiff::OnlyInFirst { index: i, constraint: constraint.clone() }),
+        }
+    }
+    for (j, constraint) in b.iter().enumerate() {
+        if !matched_b[j] {
+            diffs.push(ConstraintDiff::OnlyInSecond { index: j, constraint: constraint.clone() });
+        }
+    }
+    diffs
+}
+
+
+#[test]
+fn test_diff() {
+    use crate::examples::*;
+
+    let field_maximum = example_circuit().field_maximum.unwrap();
+    let a = example_constraints();
+    assert_eq!(diff(&a, &a, &field_maximum), vec![]);
+
+    // Reordered, with a coefficient -1 written as 100 and as 201.
+    let mut b = a.clone();
+    b.constraints.reverse();
+    b.constraints[0].linear_combination_b.values = Some(vec![201, 1]);
+    let mut a_minus = a.clone();
+    a_minus.constraints[2].linear_combination_b.values = Some(vec![100, 1]);
+    assert_eq!(diff(&a_minus, &b, &field_maximum), vec![]);
+
+    // A single coefficient differs.
+    let mut b = a.clone();
+    b.constraints[1].linear_combination_a.values = Some(vec![2]);
+    assert_eq!(diff(&a, &b, &field_maximum), vec![ConstraintDiff::CoefficientsDiffer {
+        index_a: 1,
+        index_b: 1,
+        a: ConstraintOwned { a: vec![(2, vec![1])], b: vec![(2, vec![1])], c: vec![(5, vec![1])] },
+        b: ConstraintOwned { a: vec![(2, vec![2])], b: vec![(2, vec![1])], c: vec![(5, vec![1])] },
+    }]);
+
+    // A constraint only in one system.
+    let mut b = a.clone();
+    let removed = b.constraints.remove(0);
+    b.constraints[0].linear_combination_c.variable_ids = vec![1];
+    let diffs = diff(&a, &b, &field_maximum);
+    assert_eq!(diffs.len(), 3);
+    assert_eq!(diffs[0], ConstraintDiff::OnlyInFirst { index: 0, constraint: ConstraintOwned::from(&removed) });
+    assert!(matches!(diffs[1], ConstraintDiff::OnlyInFirst { index: 1, .. }));
+    assert!(matches!(diffs[2], ConstraintDiff::OnlyInSecond { index: 0, .. }));
+}
@@ -0,0 +1,105 @@
+//! Stable digests of statements, e.g. to cache proving keys.
+
+use sha2::{Digest, Sha256};
+
+use crate::{CircuitOwned, KeyValueOwned};
+
+
+/// Compute a SHA-256 digest of the circuit definition.
+///
+/// The digest covers the connections (sorted by ID), the free variable ID, the field maximum,
+/// and the configuration (sorted by key). It does not depend on the Flatbuffers encoding, nor
+/// on the order of connections and configuration entries, nor on trailing zeros of values.
+/// Changing any connection value or the field modulus changes the digest.
+pub fn header_digest(circuit: &CircuitOwned) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"zkinterface circuit digest v1");
+
+    let mut connections: Vec<_> = circuit.connections.get_variables().into_iter()
+        .map(|var| (var.id, trim(var.value)))
+        .collect();
+    connections.sort();
+    hasher.update((connections.len() as u64).to_le_bytes());
+    for (id, value) in connections {
+        hasher.update(id.to_le_bytes());
+        update_bytes(&mut hasher, value);
+    }
+
+    hasher.update(circuit.free_variable_id.to_le_bytes());
+    update_option(&mut hasher, circuit.field_maximum.as_ref().map(|max| trim(max)));
+
+    let mut configuration: Vec<&KeyValueOwned> = circuit.configuration.iter().flatten().collect();
+    configuration.sort_by(|a, b| a.key.cmp(&b.key));
+    hasher.update((configuration.len() as u64).to_le_bytes());
+    for kv in configuration {
+        update_bytes(&mut hasher, kv.key.as_bytes());
+        update_option(&mut hasher, kv.text.as_ref().map(|text| text.as_bytes()));
+        update_option(&mut hasher, kv.data.as_deref());
+        hasher.update(kv.number.to_le_bytes());
+    }
+
+    hasher.finalize().into()
+}
+
+fn trim(value: &[u8]) -> &[u8] {
+    let len = value.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    &value[..len]
+}
+
+fn update_bytes(hasher: &mut Sha256, bytes: &[u8]) {
+    hasher.update((bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+fn update_option(hasher: &mut Sha256, bytes: Option<&[u8]>) {
+    match bytes {
+        Some(bytes) => {
+            hasher.update([1]);
+            update_bytes(hasher, bytes);
+        }
+        None => hasher.update([0]),
+    }
+}
+
+
+#[test]
+fn test_header_digest() {
+    use crate::VariablesOwned;
+    use crate::examples::*;
+
+    let kv = |key: &str, number: i64| KeyValueOwned { key: key.to_string(), text: None, data: None, number };
+
+    let mut circuit1 = example_circuit();
+    circuit1.configuration = Some(vec![kv("a", 1), kv("b", 2)]);
+
+    // The same circuit, built differently.
+    let mut circuit2 = example_circuit();
+    circuit2.connections = VariablesOwned {
+        variable_ids: vec![3, 1, 2],
+        values: Some(vec![25, 0, 3, 0, 4, 0]),
+    };
+    circuit2.field_maximum = Some(vec![100]);
+    circuit2.configuration = Some(vec![kv("b", 2), kv("a", 1)]);
+
+    assert_eq!(header_digest(&circuit1), header_digest(&circuit2));
+
+    // Through the binary format.
+    let mut buf = vec![];
+    circuit2.write_into(&mut buf).unwrap();
+    let mut messages = crate::Messages::new();
+    messages.push_message(buf).unwrap();
+    let circuit3 = CircuitOwned::from(messages.first_circuit().unwrap());
+    assert_eq!(header_digest(&circuit1), header_digest(&circuit3));
+
+    let mut other = circuit1.clone();
+    other.connections.values = Some(serialize_small(&[3u32, 4, 26]));
+    assert_ne!(header_digest(&circuit1), header_digest(&other));
+
+    let mut other = circuit1.clone();
+    other.field_maximum = Some(serialize_small(&[102u64]));
+    assert_ne!(header_digest(&circuit1), header_digest(&other));
+
+    let mut other = circuit1.clone();
+    other.configuration = Some(vec![kv("a", 1), kv("b", 3)]);
+    assert_ne!(header_digest(&circuit1), header_digest(&other));
+}
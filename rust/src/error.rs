@@ -0,0 +1,173 @@
+//! The error type of this crate.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::owned::hex;
+
+
+/// The ways in which reading, writing, or checking messages can fail.
+#[derive(Debug)]
+pub enum ZkiError {
+    /// Reading or writing failed.
+    #[cfg(feature = "std")]
+    Io(io::Error),
+    /// A buffer is not a well-formed message, or a stream ends within a message.
+    FlatbufferVerification(String),
+    /// A size prefix announces a message larger than the limit of the reader.
+    MessageTooLarge { size: usize, max: usize },
+    /// A required field or message is absent.
+    MissingField { name: String },
+    /// A value does not fit in the field, for the variable `id` and with the `value` as encoded
+    /// in the message, if known.
+    FieldOverflow { id: Option<u64>, value: Option<Vec<u8>> },
+    /// The `field_maximum` is zero, so it is not one less than a prime.
+    FieldMaximumZero { field_maximum: Vec<u8> },
+    /// The `field_maximum` is odd and not 1, so the modulus is even and not prime.
+    EvenModulus { field_maximum: Vec<u8> },
+    /// A variable is referenced but has no value.
+    DanglingVariable { id: u64 },
+    /// Variables are referenced but not defined.
+    DanglingVariables { ids: Vec<u64> },
+    /// Constraints reference variables at or above `free_variable_id`, as (constraint index, ID).
+    BoundaryViolation { free_variable_id: u64, references: Vec<(usize, u64)> },
+    /// JSON could not be parsed or produced.
+    Json(serde_json::Error),
+    /// Any other inconsistency, described by a message.
+    Other(String),
+}
+
+impl fmt::Display for ZkiError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            ZkiError::Io(err) => write!(f, "{}", err),
+            ZkiError::FlatbufferVerification(msg) => write!(f, "{}", msg),
+            ZkiError::MessageTooLarge { size, max } => write!(f, "a message of {} bytes exceeds the limit of {} bytes", size, max),
+            ZkiError::MissingField { name } => write!(f, "missing {}", name),
+            ZkiError::FieldOverflow { id: Some(id), value: Some(value) } => write!(f,
+                "the value of variable {}, with bytes {}, is too large for the field", id, hex::encode(value)),
+            ZkiError::FieldOverflow { id: Some(id), value: None } => write!(f, "the value of variable {} is too large for the field", id),
+            ZkiError::FieldOverflow { id: None, value: Some(value) } => write!(f,
+                "a value, with bytes {}, is too large for the field", hex::encode(value)),
+            ZkiError::FieldOverflow { id: None, value: None } => write!(f, "a value is too large for the field"),
+            ZkiError::FieldMaximumZero { field_maximum } => write!(f, "field_maximum, with bytes {}, is zero", hex::encode(field_maximum)),
+            ZkiError::EvenModulus { field_maximum } => write!(f,
+                "field_maximum, with bytes {}, is odd, so the modulus is even and not prime", hex::encode(field_maximum)),
+            ZkiError::DanglingVariable { id } => write!(f, "variable {} is referenced but has no value", id),
+            ZkiError::DanglingVariables { ids } => write!(f, "the constraints reference undefined variables {:?}", ids),
+            ZkiError::BoundaryViolation { free_variable_id, references } => write!(f,
+                "the constraints reference variables at or above free_variable_id {}, as (constraint, variable): {:?}",
+                free_variable_id, references),
+            ZkiError::Json(err) => write!(f, "{}", err),
+            ZkiError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for ZkiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            ZkiError::Io(err) => Some(err),
+            ZkiError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ZkiError {
+    fn from(err: io::Error) -> ZkiError {
+        ZkiError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for ZkiError {
+    fn from(err: serde_json::Error) -> ZkiError {
+        ZkiError::Json(err)
+    }
+}
+
+impl From<String> for ZkiError {
+    fn from(msg: String) -> ZkiError {
+        ZkiError::Other(msg)
+    }
+}
+
+impl From<&str> for ZkiError {
+    fn from(msg: &str) -> ZkiError {
+        ZkiError::Other(msg.to_string())
+    }
+}
+
+
+#[test]
+fn test_error_variants() {
+    use crate::examples::*;
+    use crate::{CircuitOwned, MessageReader, VariablesOwned};
+
+    // Io.
+    let err = example_circuit().write_into(&mut &mut [0u8; 4][..]).unwrap_err();
+    assert!(matches!(err, ZkiError::Io(_)));
+    assert!(err.source().is_some());
+
+    // FlatbufferVerification.
+    let err = crate::verifier::verify_size_prefixed_root(&[8, 0, 0, 0, 1]).unwrap_err();
+    assert!(matches!(err, ZkiError::FlatbufferVerification(_)));
+    let err = MessageReader::new(&[100u8, 0, 0, 0, 1, 2][..]).next_message().unwrap_err();
+    assert!(matches!(err, ZkiError::FlatbufferVerification(_)));
+
+    // MessageTooLarge.
+    let err = MessageReader::with_max_message_size(&[100u8, 0, 0, 0][..], 99).next_message().unwrap_err();
+    assert!(matches!(err, ZkiError::MessageTooLarge { size: 100, max: 99 }));
+
+    // MissingField.
+    let circuit = CircuitOwned { field_maximum: None, ..example_circuit() };
+    let err = crate::satisfaction::verify_satisfaction(&circuit, &example_constraints(), &example_witness()).unwrap_err();
+    assert!(matches!(err, ZkiError::MissingField { ref name } if name == "field_maximum"));
+
+    // FieldOverflow.
+    let mut vars = VariablesOwned { variable_ids: vec![7], values: Some(vec![250]) };
+    let err = vars.normalize_values(&[100]).unwrap_err();
+    assert!(matches!(err, ZkiError::FieldOverflow { id: Some(7), value: Some(ref value) } if value == &[250]));
+    assert_eq!(err.to_string(), "the value of variable 7, with bytes fa, is too large for the field");
+
+    // FieldMaximumZero.
+    let circuit = CircuitOwned { field_maximum: Some(vec![0, 0]), ..example_circuit() };
+    let err = circuit.validate_field().unwrap_err();
+    assert!(matches!(err, ZkiError::FieldMaximumZero { ref field_maximum } if field_maximum == &[0, 0]));
+
+    // EvenModulus.
+    let circuit = CircuitOwned { field_maximum: Some(vec![99]), ..example_circuit() };
+    let err = circuit.validate_field().unwrap_err();
+    assert!(matches!(err, ZkiError::EvenModulus { ref field_maximum } if field_maximum == &[99]));
+
+    // DanglingVariable.
+    let mut witness = example_witness();
+    witness.assigned_variables = VariablesOwned { variable_ids: vec![4], values: Some(serialize_small(&[9u32])) };
+    let err = crate::satisfaction::verify_satisfaction(&example_circuit(), &example_constraints(), &witness).unwrap_err();
+    assert!(matches!(err, ZkiError::DanglingVariable { id: 5 }));
+
+    // DanglingVariables.
+    let mut cs = example_constraints();
+    cs.constraints[0].linear_combination_c.variable_ids = vec![9];
+    let err = crate::validation::validate_ids(&example_circuit(), &cs, true).unwrap_err();
+    assert!(matches!(err, ZkiError::DanglingVariables { ref ids } if ids == &[9]));
+
+    // BoundaryViolation.
+    let err = crate::validation::check_free_boundary(&example_circuit(), &cs).unwrap_err();
+    assert!(matches!(err, ZkiError::BoundaryViolation { free_variable_id: 6, .. }));
+
+    // Json.
+    let err = crate::MessagesOwned::from_json("{").unwrap_err();
+    assert!(matches!(err, ZkiError::Json(_)));
+
+    // Other.
+    let err = crate::combined::read_combined(&mut &[][..]).unwrap_err();
+    assert!(matches!(err, ZkiError::Other(_)));
+}
@@ -1,11 +1,18 @@
-use flatbuffers::{emplace_scalar, EndianScalar, FlatBufferBuilder};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::mem::size_of;
+use flatbuffers::{emplace_scalar, EndianScalar};
+#[cfg(feature = "std")]
 use std::io;
-use std::mem::size_of;
 
-use crate::{Result, CircuitOwned, ConstraintSystemOwned, VariablesOwned};
-use crate::zkinterface_generated::zkinterface::{Message, Root, RootArgs, Variables, VariablesArgs, Witness, WitnessArgs};
+use crate::{CircuitOwned, ConstraintSystemOwned, VariablesOwned, WitnessOwned};
+#[cfg(feature = "std")]
+use crate::Result;
 
 
+/// The field of the example circuit: integers modulo 101.
+pub const MODULUS: u64 = 101;
+
 pub fn example_circuit() -> CircuitOwned {
     example_circuit_inputs(3, 4, 25)
 }
@@ -18,13 +25,13 @@ pub fn example_circuit_inputs(x: u32, y: u32, zz: u32) -> CircuitOwned {
             values: Some(serialize_small(&[x, y, zz])),
         },
         free_variable_id: 6,
-        field_maximum: None,
+        field_maximum: Some(serialize_small(&[MODULUS - 1])),
         configuration: None,
     }
 }
 
 
-pub fn write_example_constraints<W: io::Write>(mut writer: W) -> Result<()> {
+pub fn example_constraints() -> ConstraintSystemOwned {
     let constraints_vec: &[((Vec<u64>, Vec<u8>), (Vec<u64>, Vec<u8>), (Vec<u64>, Vec<u8>))] = &[
         // (A ids values)  *  (B ids values)  =  (C ids values)
         ((vec![1], vec![1]), (vec![1], vec![1]), (vec![4], vec![1])),       // x * x = xx
@@ -32,48 +39,46 @@ pub fn write_example_constraints<W: io::Write>(mut writer: W) -> Result<()> {
         ((vec![0], vec![1]), (vec![4, 5], vec![1, 1]), (vec![3], vec![1])), // 1 * (xx + yy) = z
     ];
 
-    let constraints_owned: ConstraintSystemOwned = constraints_vec.into();
+    constraints_vec.into()
+}
 
-    constraints_owned.write_into(&mut writer)
+#[cfg(feature = "std")]
+pub fn write_example_constraints<W: io::Write>(mut writer: W) -> Result<()> {
+    example_constraints().write_into(&mut writer)
+}
+
+pub fn example_witness() -> WitnessOwned {
+    example_witness_inputs(3, 4)
+}
+
+pub fn example_witness_inputs(x: u32, y: u32) -> WitnessOwned {
+    WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![4, 5], // xx, yy
+            values: Some(serialize_small(&[
+                x * x, // var_4 = xx = x^2
+                y * y, // var_5 = yy = y^2
+            ])),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
 pub fn write_example_witness<W: io::Write>(writer: W) -> Result<()> {
     write_example_witness_inputs(writer, 3, 4)
 }
 
+#[cfg(feature = "std")]
 pub fn write_example_witness_inputs<W: io::Write>(mut writer: W, x: u32, y: u32) -> Result<()> {
-    let ids = [4, 5 as u64]; // xx, yy
-    let values = serialize_small(&[
-        x * x, // var_4 = xx = x^2
-        y * y, // var_5 = yy = y^2
-    ]);
-
-    let mut builder = &mut FlatBufferBuilder::new();
-    let ids = builder.create_vector(&ids);
-    let values = builder.create_vector(&values);
-    let values = Variables::create(&mut builder, &VariablesArgs {
-        variable_ids: Some(ids),
-        values: Some(values),
-        info: None,
-    });
-    let assign = Witness::create(&mut builder, &WitnessArgs {
-        assigned_variables: Some(values),
-    });
-    let message = Root::create(&mut builder, &RootArgs {
-        message_type: Message::Witness,
-        message: Some(assign.as_union_value()),
-    });
-    builder.finish_size_prefixed(message, None);
-
-    writer.write_all(builder.finished_data())?;
-    Ok(())
+    example_witness_inputs(x, y).write_into(&mut writer)
 }
 
 pub fn serialize_small<T: EndianScalar>(values: &[T]) -> Vec<u8> {
     let sz = size_of::<T>();
     let mut buf = vec![0u8; sz * values.len()];
     for i in 0..values.len() {
-        emplace_scalar(&mut buf[sz * i..], values[i]);
+        // Safe because each value has a slot of exactly size_of::<T>() bytes.
+        unsafe { emplace_scalar(&mut buf[sz * i..], values[i]) };
     }
     buf
 }
@@ -0,0 +1,349 @@
+//! Arithmetic on field elements encoded as little-endian bytes.
+//!
+//! Results are encoded with the width of the largest element of the field.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+
+/// A non-negative integer of arbitrary size.
+/// Stored as little-endian 32-bit limbs, without trailing zero limbs.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub(crate) struct Uint {
+    limbs: Vec<u32>,
+}
+
+// Some methods are used only by the modules that need std.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+impl Uint {
+    pub fn zero() -> Uint {
+        Uint { limbs: vec![] }
+    }
+
+    pub fn one() -> Uint {
+        Uint { limbs: vec![1] }
+    }
+
+    pub fn from_le_bytes(bytes: &[u8]) -> Uint {
+        let limbs = bytes.chunks(4).map(|chunk| {
+            let mut limb = [0u8; 4];
+            limb[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(limb)
+        }).collect();
+        Uint { limbs }.normalized()
+    }
+
+    /// Little-endian bytes, padded with zeros to at least `len` bytes.
+    pub fn to_le_bytes(&self, len: usize) -> Vec<u8> {
+        let mut bytes: Vec<u8> = self.limbs.iter()
+            .flat_map(|limb| limb.to_le_bytes().to_vec())
+            .collect();
+        while bytes.len() > len && bytes.last() == Some(&0) {
+            bytes.pop();
+        }
+        bytes.resize(bytes.len().max(len), 0);
+        bytes
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    fn normalized(mut self) -> Uint {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => 32 * self.limbs.len() - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    fn bit(&self, i: usize) -> bool {
+        match self.limbs.get(i / 32) {
+            Some(limb) => (limb >> (i % 32)) & 1 == 1,
+            None => false,
+        }
+    }
+
+    pub fn add(&self, other: &Uint) -> Uint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let sum = carry
+                + *self.limbs.get(i).unwrap_or(&0) as u64
+                + *other.limbs.get(i).unwrap_or(&0) as u64;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+        limbs.push(carry as u32);
+        Uint { limbs }.normalized()
+    }
+
+    /// Compute `self - other`. The caller must ensure that `self >= other`.
+    pub fn sub(&self, other: &Uint) -> Uint {
+        debug_assert!(*self >= *other);
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for i in 0..self.limbs.len() {
+            let mut diff = self.limbs[i] as i64
+                - *other.limbs.get(i).unwrap_or(&0) as i64
+                - borrow;
+            borrow = 0;
+            if diff < 0 {
+                diff += 1 << 32;
+                borrow = 1;
+            }
+            limbs.push(diff as u32);
+        }
+        Uint { limbs }.normalized()
+    }
+
+    pub fn mul(&self, other: &Uint) -> Uint {
+        if self.is_zero() || other.is_zero() { return Uint::zero(); }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, &x) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, &y) in other.limbs.iter().enumerate() {
+                let cur = limbs[i + j] as u64 + x as u64 * y as u64 + carry;
+                limbs[i + j] = cur as u32;
+                carry = cur >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        Uint { limbs }.normalized()
+    }
+
+    fn shl1_with(&self, bit: bool) -> Uint {
+        let mut limbs = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = bit as u32;
+        for &limb in &self.limbs {
+            limbs.push((limb << 1) | carry);
+            carry = limb >> 31;
+        }
+        limbs.push(carry);
+        Uint { limbs }.normalized()
+    }
+
+    /// Compute `self mod modulus`. The modulus must not be zero.
+    pub fn rem(&self, modulus: &Uint) -> Uint {
+        assert!(!modulus.is_zero(), "modulus must not be zero");
+        if *self < *modulus { return self.clone(); }
+
+        let mut rem = Uint::zero();
+        for i in (0..self.bits()).rev() {
+            rem = rem.shl1_with(self.bit(i));
+            if rem >= *modulus {
+                rem = rem.sub(modulus);
+            }
+        }
+        rem
+    }
+
+    /// Divide by a small number, returning the quotient and the remainder.
+    fn div_rem_small(&self, divisor: u32) -> (Uint, u32) {
+        let mut limbs = vec![0u32; self.limbs.len()];
+        let mut rem = 0u64;
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let cur = (rem << 32) | limb as u64;
+            limbs[i] = (cur / divisor as u64) as u32;
+            rem = cur % divisor as u64;
+        }
+        (Uint { limbs }.normalized(), rem as u32)
+    }
+
+    /// The decimal representation.
+    pub fn to_decimal(&self) -> String {
+        let mut chunks = vec![];
+        let mut rest = self.clone();
+        loop {
+            let (quotient, chunk) = rest.div_rem_small(1_000_000_000);
+            chunks.push(chunk);
+            rest = quotient;
+            if rest.is_zero() { break; }
+        }
+        let mut text = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            text.push_str(&format!("{:09}", chunk));
+        }
+        text
+    }
+}
+
+impl Ord for Uint {
+    fn cmp(&self, other: &Uint) -> Ordering {
+        self.limbs.len().cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for Uint {
+    fn partial_cmp(&self, other: &Uint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+/// A prime field given by its modulus, which is `field_maximum + 1`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Field {
+    modulus: Uint,
+}
+
+// Some methods are used only by the modules that need std.
+#[cfg_attr(not(feature = "std"), allow(dead_code))]
+impl Field {
+    pub fn from_maximum(field_maximum: &[u8]) -> Field {
+        Field {
+            modulus: Uint::from_le_bytes(field_maximum).add(&Uint::one()),
+        }
+    }
+
+    pub fn from_modulus(modulus: &[u8]) -> Field {
+        Field {
+            modulus: Uint::from_le_bytes(modulus),
+        }
+    }
+
+    pub fn modulus(&self) -> &Uint {
+        &self.modulus
+    }
+
+    /// The number of bytes needed to encode any element of the field.
+    pub fn byte_width(&self) -> usize {
+        let maximum = self.modulus.sub(&Uint::one());
+        maximum.bits().div_ceil(8).max(1)
+    }
+
+    pub fn reduce(&self, value: &Uint) -> Uint {
+        value.rem(&self.modulus)
+    }
+
+    pub fn add(&self, a: &Uint, b: &Uint) -> Uint {
+        self.reduce(&a.add(b))
+    }
+
+    pub fn mul(&self, a: &Uint, b: &Uint) -> Uint {
+        self.reduce(&a.mul(b))
+    }
+
+    pub fn neg(&self, a: &Uint) -> Uint {
+        let a = self.reduce(a);
+        if a.is_zero() { a } else { self.modulus.sub(&a) }
+    }
+
+    pub fn sub(&self, a: &Uint, b: &Uint) -> Uint {
+        self.add(a, &self.neg(b))
+    }
+
+    /// The inverse of `a`, by Fermat's little theorem. The modulus must be prime.
+    /// Returns None if `a` is zero.
+    pub fn inv(&self, a: &Uint) -> Option<Uint> {
+        let a = self.reduce(a);
+        if a.is_zero() { return None; }
+        let exponent = self.modulus.sub(&Uint::from_le_bytes(&[2]));
+        let mut result = Uint::one();
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, &a);
+            }
+        }
+        Some(result)
+    }
+}
+
+/// Compute `-value` modulo `modulus`. The modulus must be at least 2.
+pub fn field_neg(value: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let field = Field::from_modulus(modulus);
+    field.neg(&Uint::from_le_bytes(value)).to_le_bytes(field.byte_width())
+}
+
+/// Encode a signed integer as a field element: negative values become `modulus - |value|`.
+/// The modulus must be at least 2.
+pub fn from_i64(value: i64, modulus: &[u8]) -> Vec<u8> {
+    let field = Field::from_modulus(modulus);
+    let magnitude = Uint::from_le_bytes(&value.unsigned_abs().to_le_bytes());
+    let element = if value < 0 { field.neg(&magnitude) } else { field.reduce(&magnitude) };
+    element.to_le_bytes(field.byte_width())
+}
+
+/// Compute `a + b` modulo `modulus`. The inputs may have different lengths.
+/// The modulus must be at least 2.
+pub fn field_add(a: &[u8], b: &[u8], modulus: &[u8]) -> Vec<u8> {
+    let field = Field::from_modulus(modulus);
+    field.add(&Uint::from_le_bytes(a), &Uint::from_le_bytes(b)).to_le_bytes(field.byte_width())
+}
+
+
+#[test]
+fn test_uint_arithmetic() {
+    let a = Uint::from_le_bytes(&[0xff, 0xff, 0xff, 0xff, 0xff]);
+    let b = Uint::from_le_bytes(&[1, 0, 0]);
+    assert_eq!(a.add(&b), Uint::from_le_bytes(&[0, 0, 0, 0, 0, 1]));
+    assert_eq!(a.add(&b).sub(&b), a);
+    assert_eq!(a.mul(&Uint::zero()), Uint::zero());
+    assert_eq!(Uint::from_le_bytes(&[0, 0, 0]), Uint::zero());
+    assert_eq!(b.to_le_bytes(0), vec![1]);
+    assert_eq!(b.to_le_bytes(3), vec![1, 0, 0]);
+    assert_eq!(Uint::zero().to_le_bytes(2), vec![0, 0]);
+
+    let field = Field::from_maximum(&[100]); // Modulus 101.
+    let x = Uint::from_le_bytes(&[50]);
+    assert_eq!(field.add(&x, &x), Uint::from_le_bytes(&[100]));
+    assert_eq!(field.add(&x, &x.add(&Uint::one())), Uint::zero());
+    assert_eq!(field.mul(&x, &x), Uint::from_le_bytes(&[(2500 % 101) as u8]));
+    assert_eq!(field.sub(&Uint::one(), &x), Uint::from_le_bytes(&[52]));
+    assert_eq!(field.inv(&x), Some(Uint::from_le_bytes(&[99]))); // 50 * 99 = 4950 = 1 mod 101.
+    assert_eq!(field.inv(&Uint::one()), Some(Uint::one()));
+    assert_eq!(field.inv(&Uint::from_le_bytes(&[101])), None);
+
+    assert_eq!(Uint::zero().to_decimal(), "0");
+    assert_eq!(Uint::from_le_bytes(&[0x00, 0xca, 0x9a, 0x3b]).to_decimal(), "1000000000");
+
+    let big = Uint::from_le_bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 1]); // 2^64
+    assert_eq!(big.to_decimal(), "18446744073709551616");
+    assert_eq!(field.reduce(&big), Uint::from_le_bytes(&[((1u128 << 64) % 101) as u8]));
+}
+
+#[test]
+fn test_field_neg_add() {
+    let modulus = [0x01, 0x01]; // 257
+    assert_eq!(field_neg(&[1], &modulus), vec![0x00, 0x01]);
+    assert_eq!(field_neg(&[0x00, 0x01], &modulus), vec![1, 0]);
+    assert_eq!(field_neg(&[], &modulus), vec![0, 0]);
+    assert_eq!(field_neg(&[0x01, 0x01, 0, 0], &modulus), vec![0, 0]);
+
+    assert_eq!(field_add(&[0xff], &[2, 0, 0, 0], &modulus), vec![0, 0]);
+    assert_eq!(field_add(&[0x00, 0x01], &[0x00, 0x01], &modulus), vec![0xff, 0x00]);
+    assert_eq!(field_add(&[5], &[], &modulus), vec![5, 0]);
+    assert_eq!(field_add(&[7], &field_neg(&[7], &modulus), &modulus), vec![0, 0]);
+
+    let modulus = [101];
+    assert_eq!(field_neg(&[100], &modulus), vec![1]);
+    assert_eq!(field_add(&[100], &[100], &modulus), vec![99]);
+}
+
+#[test]
+fn test_from_i64() {
+    let modulus = [101];
+    assert_eq!(from_i64(-1, &modulus), vec![100]);
+    assert_eq!(from_i64(0, &modulus), vec![0]);
+    assert_eq!(from_i64(5, &modulus), vec![5]);
+    assert_eq!(from_i64(-101, &modulus), vec![0]);
+    assert_eq!(from_i64(202, &modulus), vec![0]);
+
+    // 2^64 + 1.
+    let modulus = [1, 0, 0, 0, 0, 0, 0, 0, 1];
+    assert_eq!(from_i64(-1, &modulus), vec![0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(from_i64(i64::MIN, &modulus), vec![1, 0, 0, 0, 0, 0, 0, 0x80, 0]);
+    assert_eq!(from_i64(i64::MAX, &modulus), vec![0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f, 0]);
+}
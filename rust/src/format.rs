@@ -0,0 +1,89 @@
+//! Human-readable text of constraint systems.
+
+use std::collections::HashMap;
+
+use crate::{CircuitOwned, ConstraintSystemOwned, VariablesOwned};
+use crate::field::Uint;
+
+
+/// The configuration key of the table of variable names.
+/// Its text is a JSON object from variable IDs to names, like `{"1": "x", "2": "y"}`.
+pub const VARIABLE_NAMES_KEY: &str = "variable_names";
+
+/// The names of variables declared in the configuration of the circuit, if any.
+/// An invalid table is ignored.
+pub fn variable_names(circuit: &CircuitOwned) -> HashMap<u64, String> {
+    circuit.get_config_string(VARIABLE_NAMES_KEY)
+        .and_then(|text| serde_json::from_str(text).ok())
+        .unwrap_or_default()
+}
+
+/// Print each constraint on a line, like `(2*x1 + x3) * (x2) = (x4 + 5)`.
+///
+/// Variables are printed by their name in the configuration of the circuit (see
+/// `VARIABLE_NAMES_KEY`), else as `x<id>`. Coefficients are decimal and omitted if 1.
+/// Terms of the constant one (ID 0) are printed as their coefficient.
+/// An empty linear combination is printed as `0`.
+pub fn format_constraints(circuit: &CircuitOwned, cs: &ConstraintSystemOwned) -> String {
+    let names = variable_names(circuit);
+    let mut text = String::new();
+    for constraint in &cs.constraints {
+        text.push_str(&format!(
+            "({}) * ({}) = ({})\n",
+            format_lc(&constraint.linear_combination_a, &names),
+            format_lc(&constraint.linear_combination_b, &names),
+            format_lc(&constraint.linear_combination_c, &names),
+        ));
+    }
+    text
+}
+
+fn format_lc(lc: &VariablesOwned, names: &HashMap<u64, String>) -> String {
+    let terms: Vec<String> = lc.get_variables().iter().map(|term| {
+        let coeff = Uint::from_le_bytes(term.value).to_decimal();
+        if term.id == 0 {
+            return coeff;
+        }
+        let name = match names.get(&term.id) {
+            Some(name) => name.clone(),
+            None => format!("x{}", term.id),
+        };
+        if coeff == "1" { name } else { format!("{}*{}", coeff, name) }
+    }).collect();
+
+    if terms.is_empty() { "0".to_string() } else { terms.join(" + ") }
+}
+
+
+#[test]
+fn test_format_constraints() {
+    use crate::examples::*;
+    use crate::KeyValueOwned;
+
+    let mut circuit = example_circuit();
+    let mut cs = example_constraints();
+    cs.constraints[0].linear_combination_a.values = Some(vec![2]);
+    cs.constraints[1].linear_combination_c = VariablesOwned::default();
+    cs.constraints[2].linear_combination_b = VariablesOwned {
+        variable_ids: vec![4, 5, 0],
+        values: Some(vec![1, 1, 3]),
+    };
+
+    assert_eq!(format_constraints(&circuit, &cs), "\
+(2*x1) * (x1) = (x4)
+(x2) * (x2) = (0)
+(1) * (x4 + x5 + 3) = (x3)
+");
+
+    circuit.configuration = Some(vec![KeyValueOwned {
+        key: VARIABLE_NAMES_KEY.to_string(),
+        text: Some(r#"{"1": "x", "4": "xx"}"#.to_string()),
+        data: None,
+        number: 0,
+    }]);
+    assert_eq!(format_constraints(&circuit, &cs), "\
+(2*x) * (x) = (xx)
+(x2) * (x2) = (0)
+(1) * (xx + x5 + 3) = (x3)
+");
+}
@@ -0,0 +1,152 @@
+//! Call a gadget in a child process, over its standard input and output.
+
+use std::process::Child;
+
+use crate::{Result, CircuitOwned, CommandOwned, ConstraintSystemOwned, MessageOwned, MessageReader, VariablesOwned, WitnessOwned};
+use crate::owned::constraints::LinComb;
+
+
+/// Send a circuit and a command to a gadget, and collect its response.
+///
+/// The circuit then the command are written to the stdin of the child, which is then closed.
+/// The response is read from the stdout of the child until the end of the stream. Several
+/// ConstraintSystem or Witness messages are concatenated, and Circuit or Command messages
+/// are ignored. The child must be spawned with piped stdin and stdout.
+///
+/// Fails if the gadget closes its input before reading the whole request, if the response
+/// misses the constraints or the witness requested by the command, or if the child exits with
+/// an error.
+pub fn call_gadget(command: &CommandOwned, circuit: &CircuitOwned, child: &mut Child) -> Result<(ConstraintSystemOwned, WitnessOwned)> {
+    let mut stdin = child.stdin.take()
+        .ok_or("the stdin of the gadget is not piped")?;
+    let write = circuit.write_into(&mut stdin)
+        .and_then(|()| command.write_into(&mut stdin));
+    drop(stdin);
+    if let Err(err) = write {
+        // Do not leave the child running or unreaped.
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err(format!("the gadget closed its input early: {}", err).into());
+    }
+
+    let mut cs: Option<ConstraintSystemOwned> = None;
+    let mut witness_terms: Option<LinComb> = None;
+    let mut witness_has_values = true;
+    let read = (|| -> Result<()> {
+        let stdout = child.stdout.as_mut()
+            .ok_or("the stdout of the gadget is not piped")?;
+        let mut reader = MessageReader::new(stdout);
+        while let Some(message) = reader.next_message()? {
+            match message {
+                MessageOwned::ConstraintSystem(msg) => {
+                    cs.get_or_insert_with(ConstraintSystemOwned::default)
+                        .constraints.extend(msg.constraints);
+                }
+                MessageOwned::Witness(msg) => {
+                    witness_has_values &= msg.assigned_variables.values.is_some();
+                    witness_terms.get_or_insert_with(LinComb::new)
+                        .extend(LinComb::from(&msg.assigned_variables));
+                }
+                MessageOwned::Circuit(_) | MessageOwned::Command(_) => {}
+            }
+        }
+        Ok(())
+    })();
+
+    // Do not leave the child running or unreaped, even if its output is invalid.
+    if read.is_err() {
+        let _ = child.kill();
+    }
+    let status = child.wait()?;
+    read?;
+    if !status.success() {
+        return Err(format!("the gadget failed with {}", status).into());
+    }
+
+    if command.constraints_generation && cs.is_none() {
+        return Err("the gadget closed its output before sending a ConstraintSystem message".into());
+    }
+    if command.witness_generation && witness_terms.is_none() {
+        return Err("the gadget closed its output before sending a Witness message".into());
+    }
+
+    let witness_terms = witness_terms.unwrap_or_default();
+    let assigned_variables = if witness_has_values {
+        VariablesOwned::from(&witness_terms)
+    } else {
+        VariablesOwned {
+            variable_ids: witness_terms.iter().map(|(id, _)| *id).collect(),
+            values: None,
+        }
+    };
+    Ok((cs.unwrap_or_default(), WitnessOwned { assigned_variables }))
+}
+
+
+#[cfg(unix)]
+#[test]
+fn test_call_gadget() {
+    use std::process::{Command, Stdio};
+    use crate::examples::*;
+    use crate::KeyValueOwned;
+
+    let dir = std::env::temp_dir().join(format!("zkinterface_test_gadget_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let response = dir.join("response.zkif");
+    let mut buf = Vec::<u8>::new();
+    example_constraints().write_into(&mut buf).unwrap();
+    example_witness().write_into(&mut buf).unwrap();
+    std::fs::write(&response, &buf).unwrap();
+
+    // A mock gadget that consumes the request and replies with a fixed response.
+    let spawn = |script: &str| {
+        Command::new("sh")
+            .arg("-c").arg(script)
+            .arg("gadget").arg(&response)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn().unwrap()
+    };
+    // Larger than a pipe buffer, so the write blocks until the pipe is closed.
+    let mut big_circuit = example_circuit();
+    big_circuit.configuration = Some(vec![KeyValueOwned {
+        key: "padding".to_string(),
+        text: None,
+        data: Some(vec![0; 1 << 20]),
+        number: 0,
+    }]);
+    let command = CommandOwned {
+        constraints_generation: true,
+        witness_generation: true,
+        parameters: None,
+    };
+
+    let mut child = spawn(r#"cat > /dev/null; cat "$1""#);
+    let (cs, witness) = call_gadget(&command, &example_circuit(), &mut child).unwrap();
+    assert_eq!(cs, example_constraints());
+    assert_eq!(witness, example_witness());
+
+    // The gadget stops within a message.
+    let mut child = spawn(&format!(r#"cat > /dev/null; head -c {} "$1""#, buf.len() - 3));
+    let err = call_gadget(&command, &example_circuit(), &mut child).unwrap_err();
+    assert!(err.to_string().contains("truncated message"));
+
+    // The gadget sends nothing.
+    let mut child = spawn(r#"cat > /dev/null"#);
+    let err = call_gadget(&command, &example_circuit(), &mut child).unwrap_err();
+    assert_eq!(err.to_string(), "the gadget closed its output before sending a ConstraintSystem message");
+
+    // The gadget exits without reading its input.
+    let mut child = spawn(r#"exec 0<&-; cat "$1""#);
+    let err = call_gadget(&command, &big_circuit, &mut child).unwrap_err();
+    assert!(err.to_string().starts_with("the gadget closed its input early"));
+    // The child is reaped.
+    assert!(child.try_wait().unwrap().is_some());
+
+    // The gadget fails.
+    let mut child = spawn(r#"cat > /dev/null; cat "$1"; exit 3"#);
+    let err = call_gadget(&command, &example_circuit(), &mut child).unwrap_err();
+    assert!(err.to_string().starts_with("the gadget failed with"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
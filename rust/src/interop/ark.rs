@@ -0,0 +1,145 @@
+//! Import constraint systems from arkworks.
+
+use ark_ff::{BigInteger, PrimeField};
+use ark_relations::r1cs::ConstraintSystemRef;
+
+use crate::{Result, CircuitOwned, ConstraintSystemOwned, ConstraintOwned, VariablesOwned, WitnessOwned};
+use crate::owned::constraints::LinComb;
+
+
+/// Convert an arkworks constraint system into a statement.
+///
+/// The linear combinations of the constraint system are inlined first.
+/// The constant one becomes the variable 0, the public inputs become the connections
+/// with IDs 1, 2, …, and the witness variables follow them.
+/// Values are encoded with the byte width of the field, and coefficients are trimmed of
+/// trailing zeros.
+/// In setup mode, the connections and the witness have no values.
+pub fn from_ark_cs<F: PrimeField>(cs: ConstraintSystemRef<F>) -> Result<(CircuitOwned, ConstraintSystemOwned, WitnessOwned)> {
+    cs.inline_all_lcs();
+    let matrices = cs.to_matrices()
+        .ok_or("the arkworks constraint system does not construct matrices")?;
+    let inner = cs.borrow()
+        .ok_or("the arkworks constraint system is empty")?;
+
+    let num_instance = matrices.num_instance_variables as u64;
+    let num_witness = matrices.num_witness_variables as u64;
+
+    // Skip the constant one at index 0.
+    let instance_values = inner.instance_assignment.get(1..).unwrap_or(&[]);
+    let width = trimmed_le_bytes(F::MODULUS).len();
+    let connections = to_variables(1..num_instance, instance_values, width);
+    let assigned_variables = to_variables(num_instance..num_instance + num_witness, &inner.witness_assignment, width);
+
+    let mut field_maximum = F::MODULUS;
+    field_maximum.sub_with_borrow(&F::BigInt::from(1u64));
+
+    let circuit = CircuitOwned {
+        connections,
+        free_variable_id: num_instance + num_witness,
+        field_maximum: Some(trimmed_le_bytes(field_maximum)),
+        configuration: None,
+    };
+
+    let to_lincomb = |row: &Vec<(F, usize)>| -> LinComb {
+        row.iter().map(|(coeff, index)| (*index as u64, field_to_le_bytes(coeff))).collect()
+    };
+    let constraints = matrices.a.iter()
+        .zip(matrices.b.iter())
+        .zip(matrices.c.iter())
+        .map(|((a, b), c)| ConstraintOwned {
+            a: to_lincomb(a),
+            b: to_lincomb(b),
+            c: to_lincomb(c),
+        })
+        .collect();
+
+    Ok((circuit, constraints, WitnessOwned { assigned_variables }))
+}
+
+/// Variables with consecutive IDs, with values of `width` bytes. If the values are missing,
+/// none are set.
+///
+/// Values are not trimmed: if all of them were zero, they would be encoded as empty, which
+/// reads as unassigned.
+fn to_variables<F: PrimeField>(ids: std::ops::Range<u64>, values: &[F], width: usize) -> VariablesOwned {
+    let lincomb: LinComb = ids.clone()
+        .zip(values.iter())
+        .map(|(id, value)| {
+            let mut bytes = value.into_bigint().to_bytes_le();
+            bytes.resize(width, 0);
+            (id, bytes)
+        })
+        .collect();
+
+    if lincomb.len() as u64 == ids.end - ids.start {
+        VariablesOwned::from(&lincomb)
+    } else {
+        VariablesOwned {
+            variable_ids: ids.collect(),
+            values: None,
+        }
+    }
+}
+
+/// Little-endian bytes of a coefficient, trimmed of trailing zeros.
+fn field_to_le_bytes<F: PrimeField>(value: &F) -> Vec<u8> {
+    trimmed_le_bytes(value.into_bigint())
+}
+
+fn trimmed_le_bytes(big: impl BigInteger) -> Vec<u8> {
+    let mut bytes = big.to_bytes_le();
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    bytes
+}
+
+
+#[test]
+fn test_from_ark_cs() {
+    use ark_relations::lc;
+    use ark_relations::r1cs::{ConstraintSystem, Variable};
+    use ark_test_curves::bls12_381::Fr;
+    use crate::examples::serialize_small;
+    use crate::satisfaction::verify_satisfaction;
+
+    // x * (y + 2) = z, with z public.
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let z = cs.new_input_variable(|| Ok(Fr::from(30u64))).unwrap();
+    let x = cs.new_witness_variable(|| Ok(Fr::from(5u64))).unwrap();
+    let y = cs.new_witness_variable(|| Ok(Fr::from(4u64))).unwrap();
+    let y_plus_2 = lc!() + y + (Fr::from(2u64), Variable::One);
+    cs.enforce_constraint(lc!() + x, y_plus_2, lc!() + z).unwrap();
+
+    let (circuit, constraints, witness) = from_ark_cs(cs).unwrap();
+
+    assert_eq!(circuit.connections.variable_ids, vec![1]);
+    assert_eq!(circuit.free_variable_id, 4);
+    assert_eq!(circuit.field_maximum.as_ref().unwrap().len(), 32);
+    assert_eq!(circuit.connections.get_value(1).unwrap(), &serialize_small(&[30u64, 0, 0, 0])[..]);
+    assert_eq!(witness.assigned_variables.variable_ids, vec![2, 3]);
+    assert_eq!(witness.assigned_variables.get_value(2).unwrap(), &serialize_small(&[5u64, 0, 0, 0])[..]);
+    assert_eq!(witness.assigned_variables.get_value(3).unwrap(), &serialize_small(&[4u64, 0, 0, 0])[..]);
+    assert_eq!(constraints.constraints.len(), 1);
+    // Coefficients are trimmed.
+    assert_eq!(constraints.constraints[0].linear_combination_b.values, Some(vec![2, 1]));
+
+    verify_satisfaction(&circuit, &constraints, &witness).unwrap();
+
+    let mut wrong = witness.clone();
+    wrong.assigned_variables.values.as_mut().unwrap()[32] = 3;
+    assert!(verify_satisfaction(&circuit, &constraints, &wrong).is_err());
+
+    // x * x = z, with zero values.
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let z = cs.new_input_variable(|| Ok(Fr::from(0u64))).unwrap();
+    let x = cs.new_witness_variable(|| Ok(Fr::from(0u64))).unwrap();
+    cs.enforce_constraint(lc!() + x, lc!() + x, lc!() + z).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+
+    let (circuit, constraints, witness) = from_ark_cs(cs).unwrap();
+    assert_eq!(circuit.connections.values, Some(vec![0; 32]));
+    assert_eq!(witness.assigned_variables.values, Some(vec![0; 32]));
+    verify_satisfaction(&circuit, &constraints, &witness).unwrap();
+}
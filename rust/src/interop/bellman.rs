@@ -0,0 +1,196 @@
+//! Export statements to the bellman library.
+
+use std::collections::HashMap;
+
+use ::bellman::{Circuit, ConstraintSystem, LinearCombination, SynthesisError, Variable};
+use ff::{PrimeField, PrimeFieldRepr, ScalarEngine};
+
+use crate::{Result, ZkiError, CircuitOwned, ConstraintSystemOwned, VariablesOwned, WitnessOwned};
+
+
+/// A linear combination, as terms (variable ID, coefficient).
+pub type BellmanLinComb<E> = Vec<(u64, <E as ScalarEngine>::Fr)>;
+
+/// A zkInterface statement, converted into the field of a bellman engine.
+/// Implements `bellman::Circuit`.
+pub struct BellmanCircuit<E: ScalarEngine> {
+    /// The connections of the circuit, which become the public inputs.
+    pub inputs: Vec<(u64, Option<E::Fr>)>,
+    /// The witness variables, which become auxiliary variables.
+    pub auxiliary: Vec<(u64, Option<E::Fr>)>,
+    /// The constraints A * B = C.
+    pub constraints: Vec<[BellmanLinComb<E>; 3]>,
+}
+
+/// Convert a statement for bellman.
+///
+/// The field of the circuit, if specified, must be the scalar field of the engine.
+/// The witness may be without values to generate parameters.
+/// Every variable used in the constraints must be the constant one (ID 0),
+/// a connection, or a witness variable.
+pub fn to_bellman<E: ScalarEngine>(circuit: &CircuitOwned, cs: &ConstraintSystemOwned, witness: &WitnessOwned) -> Result<BellmanCircuit<E>> {
+    if let Some(ref field_maximum) = circuit.field_maximum {
+        let mut modulus = E::Fr::char();
+        modulus.sub_noborrow(&1.into());
+        if le_bytes_to_repr::<E::Fr>(field_maximum)? != modulus {
+            return Err("the field of the circuit is not the scalar field of the engine".into());
+        }
+    }
+
+    let inputs = to_assignment::<E>(&circuit.connections)?;
+    let auxiliary = to_assignment::<E>(&witness.assigned_variables)?;
+
+    let mut known = HashMap::new();
+    known.insert(0, ());
+    for &(id, _) in inputs.iter().chain(auxiliary.iter()) {
+        if known.insert(id, ()).is_some() {
+            return Err(format!("variable {} is assigned more than once", id).into());
+        }
+    }
+
+    let mut constraints = Vec::with_capacity(cs.constraints.len());
+    for constraint in &cs.constraints {
+        let mut lcs: [BellmanLinComb<E>; 3] = [vec![], vec![], vec![]];
+        for (lc, lc_owned) in lcs.iter_mut().zip([
+            &constraint.linear_combination_a,
+            &constraint.linear_combination_b,
+            &constraint.linear_combination_c,
+        ].iter()) {
+            for term in lc_owned.get_variables() {
+                if !known.contains_key(&term.id) {
+                    return Err(ZkiError::DanglingVariable { id: term.id });
+                }
+                lc.push((term.id, le_bytes_to_fr::<E::Fr>(term.value)?));
+            }
+        }
+        constraints.push(lcs);
+    }
+
+    Ok(BellmanCircuit { inputs, auxiliary, constraints })
+}
+
+impl<E: ScalarEngine> BellmanCircuit<E> {
+    /// The values of the public inputs, to verify a proof.
+    pub fn public_inputs(&self) -> Option<Vec<E::Fr>> {
+        self.inputs.iter().map(|(_, value)| *value).collect()
+    }
+}
+
+impl<E: ScalarEngine> Circuit<E> for BellmanCircuit<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(self, cs: &mut CS) -> std::result::Result<(), SynthesisError> {
+        let mut vars = HashMap::new();
+        vars.insert(0, CS::one());
+
+        for (id, value) in self.inputs {
+            let var = cs.alloc_input(|| format!("input_{}", id), || value.ok_or(SynthesisError::AssignmentMissing))?;
+            vars.insert(id, var);
+        }
+        for (id, value) in self.auxiliary {
+            let var = cs.alloc(|| format!("aux_{}", id), || value.ok_or(SynthesisError::AssignmentMissing))?;
+            vars.insert(id, var);
+        }
+
+        let to_lc = |terms: &[(u64, E::Fr)], vars: &HashMap<u64, Variable>| {
+            terms.iter().fold(LinearCombination::zero(), |lc, (id, coeff)| lc + (*coeff, vars[id]))
+        };
+
+        for (i, [a, b, c]) in self.constraints.iter().enumerate() {
+            cs.enforce(
+                || format!("constraint_{}", i),
+                |_| to_lc(a, &vars),
+                |_| to_lc(b, &vars),
+                |_| to_lc(c, &vars),
+            );
+        }
+        Ok(())
+    }
+}
+
+fn to_assignment<E: ScalarEngine>(vars: &VariablesOwned) -> Result<Vec<(u64, Option<E::Fr>)>> {
+    vars.get_variables().iter().map(|var| {
+        let value = if var.has_value() {
+            Some(le_bytes_to_fr::<E::Fr>(var.value).map_err(|_| ZkiError::FieldOverflow {
+                id: Some(var.id),
+                value: Some(var.value.to_vec()),
+            })?)
+        } else { None };
+        Ok((var.id, value))
+    }).collect()
+}
+
+fn le_bytes_to_repr<F: PrimeField>(bytes: &[u8]) -> Result<F::Repr> {
+    let mut repr = F::Repr::default();
+    {
+        let limbs = repr.as_mut();
+        for (i, &byte) in bytes.iter().enumerate() {
+            if byte == 0 { continue; }
+            match limbs.get_mut(i / 8) {
+                Some(limb) => *limb |= (byte as u64) << (8 * (i % 8)),
+                None => return Err(ZkiError::FieldOverflow { id: None, value: Some(bytes.to_vec()) }),
+            }
+        }
+    }
+    Ok(repr)
+}
+
+/// Convert a little-endian value into a field element. Values out of the field are an error.
+pub fn le_bytes_to_fr<F: PrimeField>(bytes: &[u8]) -> Result<F> {
+    let repr = le_bytes_to_repr::<F>(bytes)?;
+    F::from_repr(repr).map_err(|_| ZkiError::FieldOverflow { id: None, value: Some(bytes.to_vec()) })
+}
+
+
+#[test]
+fn test_bellman_prove_verify() {
+    use ::bellman::groth16::{create_random_proof, generate_random_parameters, prepare_verifying_key, verify_proof};
+    use pairing::bls12_381::Bls12;
+    use rand_core::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use crate::examples::serialize_small;
+
+    // x * y = z, with z public.
+    let circuit = CircuitOwned {
+        connections: VariablesOwned {
+            variable_ids: vec![1],
+            values: Some(serialize_small(&[42u32])),
+        },
+        free_variable_id: 4,
+        field_maximum: None,
+        configuration: None,
+    };
+    let cs = ConstraintSystemOwned::from(&[
+        ((vec![2], vec![1]), (vec![3], vec![1]), (vec![1], vec![1])),
+    ][..]);
+    let witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![2, 3],
+            values: Some(serialize_small(&[6u32, 7])),
+        },
+    };
+
+    let rng = &mut XorShiftRng::from_seed([7; 16]);
+
+    let setup_witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![2, 3],
+            values: None,
+        },
+    };
+    let params = generate_random_parameters(
+        to_bellman::<Bls12>(&circuit, &cs, &setup_witness).unwrap(), rng).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let statement = to_bellman::<Bls12>(&circuit, &cs, &witness).unwrap();
+    let inputs = statement.public_inputs().unwrap();
+    let proof = create_random_proof(statement, &params, rng).unwrap();
+    assert!(verify_proof(&pvk, &proof, &inputs).unwrap());
+
+    let wrong_inputs = [le_bytes_to_fr(&[43]).unwrap()];
+    assert!(!verify_proof(&pvk, &proof, &wrong_inputs).unwrap());
+
+    // Range checks.
+    assert!(le_bytes_to_fr::<pairing::bls12_381::Fr>(&[0xff; 32]).is_err());
+    let mut wrong_field = circuit.clone();
+    wrong_field.field_maximum = Some(vec![100]);
+    assert!(to_bellman::<Bls12>(&wrong_field, &cs, &witness).is_err());
+}
@@ -0,0 +1,7 @@
+//! Bridges to other proving system libraries. Each is behind a cargo feature of the same name.
+
+#[cfg(feature = "bellman")]
+pub mod bellman;
+
+#[cfg(feature = "ark")]
+pub mod ark;
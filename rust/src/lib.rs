@@ -1,27 +1,79 @@
+//! Without the default feature `std`, the crate builds with `core` and `alloc` only: the owned
+//! messages with their Flatbuffers and serde conversions, `Messages`, and `verifier`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 pub extern crate flatbuffers;
 pub extern crate serde;
 
-#[allow(unused_imports)]
+#[allow(unused_imports, unknown_lints, mismatched_lifetime_syntaxes)]
 pub mod zkinterface_generated;
 
 pub mod reading;
+#[cfg(feature = "std")]
+pub mod writing;
+pub mod verifier;
 pub mod owned;
+#[cfg(feature = "std")]
 pub mod statement;
+#[cfg(feature = "std")]
 pub mod stats;
 pub mod examples;
+#[cfg(feature = "std")]
+pub mod satisfaction;
+#[cfg(feature = "std")]
+pub mod validation;
+#[cfg(feature = "std")]
+pub mod digest;
+#[cfg(feature = "std")]
+pub mod workspace;
+#[cfg(feature = "std")]
+pub mod combined;
+#[cfg(feature = "std")]
+pub mod interop;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod builder;
+pub mod field;
+#[cfg(feature = "std")]
+pub mod format;
+#[cfg(feature = "std")]
+pub mod transform;
+#[cfg(feature = "std")]
+pub mod gadget;
+#[cfg(feature = "std")]
+pub mod diff;
+#[cfg(feature = "std")]
+pub mod solver;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+#[cfg(feature = "compression")]
+pub mod compression;
+#[cfg(feature = "bigint")]
+pub mod bigint;
 
+pub use error::ZkiError;
 pub use reading::Messages;
+#[cfg(feature = "std")]
+pub use reading::MessageReader;
+pub use verifier::{parse_message, peek_message_type, MessageKind};
+#[cfg(feature = "std")]
+pub use writing::WitnessWriter;
+#[cfg(feature = "std")]
+pub use workspace::Workspace;
+#[cfg(feature = "std")]
+pub use builder::CircuitBuilder;
 pub use owned::{
     circuit::CircuitOwned,
     command::CommandOwned,
-    constraints::ConstraintSystemOwned,
+    constraints::{ConstraintSystemOwned, ConstraintOwned},
     keyvalue::KeyValueOwned,
-    message::MessagesOwned,
-    variables::VariablesOwned,
+    message::{MessageOwned, MessagesOwned},
+    variables::{Endianness, VariablesOwned},
     witness::WitnessOwned,
 };
 
 // Common definitions.
-use std::error::Error;
-
-pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
\ No newline at end of file
+pub type Result<T> = core::result::Result<T, ZkiError>;
\ No newline at end of file
@@ -0,0 +1,109 @@
+//! Zero-copy access to the messages of large files.
+
+use std::fs::File;
+use std::ops::Range;
+use std::path::Path;
+
+use flatbuffers::SIZE_UOFFSET;
+use memmap2::Mmap;
+
+use crate::zkinterface_generated::zkinterface::{Circuit, Root, size_prefixed_root_as_root_unchecked};
+use crate::reading::read_size_prefix;
+use crate::verifier::verify_size_prefixed_root;
+use crate::Result;
+
+
+/// The messages of a memory-mapped file. Owns the mapping, and borrows message views from it.
+pub struct MappedMessages {
+    mmap: Mmap,
+    ranges: Vec<Range<usize>>,
+}
+
+/// Map a file of size-prefixed messages, and verify all of them.
+///
+/// A message of size zero ends the file, as with `MessageReader`.
+///
+/// # Safety
+///
+/// The file must not be truncated or modified, by this or another process, as long as the
+/// returned `MappedMessages` exists. The messages are verified once when mapped; a change of
+/// the file afterwards changes the borrowed views, which is undefined behavior. This cannot be
+/// checked here, so the caller must ensure it, for instance by mapping only files that nothing
+/// else writes to.
+pub unsafe fn load_mmap(path: &Path) -> Result<MappedMessages> {
+    let file = File::open(path)?;
+    // Safety: the file is not modified while mapped, as required from the caller.
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    let mut ranges = vec![];
+    let mut pos = 0;
+    while pos < mmap.len() {
+        let rest = &mmap[pos..];
+        if rest.len() >= SIZE_UOFFSET && read_size_prefix(rest) == SIZE_UOFFSET { break; }
+        verify_size_prefixed_root(rest)?;
+        let size = read_size_prefix(rest);
+        ranges.push(pos..pos + size);
+        pos += size;
+    }
+    Ok(MappedMessages { mmap, ranges })
+}
+
+impl MappedMessages {
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The buffer of a message, with its size prefix.
+    pub fn buffer(&self, i: usize) -> Option<&[u8]> {
+        self.ranges.get(i).map(|range| &self.mmap[range.clone()])
+    }
+
+    pub fn get(&self, i: usize) -> Option<Root<'_>> {
+        // Safe because every range was verified by load_mmap.
+        self.buffer(i).map(|buf| unsafe { size_prefixed_root_as_root_unchecked(buf) })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=Root<'_>> + '_ {
+        (0..self.len()).filter_map(move |i| self.get(i))
+    }
+
+    pub fn first_circuit(&self) -> Option<Circuit<'_>> {
+        self.iter().find_map(|root| root.message_as_circuit())
+    }
+}
+
+
+#[test]
+fn test_load_mmap() {
+    use std::io::Write;
+    use crate::examples::*;
+
+    let dir = std::env::temp_dir().join(format!("zkinterface_test_mmap_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("statement.zkif");
+    let broken_path = dir.join("broken.zkif");
+
+    let mut file = File::create(&path).unwrap();
+    example_constraints().write_into(&mut file).unwrap();
+    example_circuit().write_into(&mut file).unwrap();
+    example_witness().write_into(&mut file).unwrap();
+    drop(file);
+    File::create(&broken_path).unwrap().write_all(&[100, 0, 0, 0, 1, 2]).unwrap();
+
+    // Safety: the test files are not modified while mapped.
+    let messages = unsafe { load_mmap(&path) }.unwrap();
+    let broken = unsafe { load_mmap(&broken_path) };
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(messages.len(), 3);
+    let circuit = messages.first_circuit().unwrap();
+    assert_eq!(circuit.free_variable_id(), 6);
+    assert!(messages.get(2).unwrap().message_as_witness().is_some());
+    assert!(messages.get(3).is_none());
+
+    assert!(broken.is_err());
+}
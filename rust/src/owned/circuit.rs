@@ -1,6 +1,10 @@
 //! Helpers to write messages.
 
+use alloc::collections::BTreeMap;
+use alloc::string::ToString;
+use alloc::vec::Vec;
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
+#[cfg(feature = "std")]
 use std::io::Write;
 use serde::{Deserialize, Serialize};
 use crate::zkinterface_generated::zkinterface::{
@@ -10,9 +14,12 @@ use crate::zkinterface_generated::zkinterface::{
     Root,
     RootArgs,
 };
-use super::variables::VariablesOwned;
+use super::variables::{Endianness, VariablesOwned};
 use super::keyvalue::KeyValueOwned;
-use crate::Result;
+#[cfg(feature = "std")]
+use super::message::MessageOwned;
+use crate::field::Uint;
+use crate::{Result, ZkiError};
 
 
 #[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize)]
@@ -21,6 +28,7 @@ pub struct CircuitOwned {
 
     pub free_variable_id: u64,
 
+    #[serde(with = "super::hex")]
     pub field_maximum: Option<Vec<u8>>,
 
     pub configuration: Option<Vec<KeyValueOwned>>,
@@ -32,12 +40,58 @@ impl<'a> From<Circuit<'a>> for CircuitOwned {
         CircuitOwned {
             connections: VariablesOwned::from(circuit_ref.connections().unwrap()),
             free_variable_id: circuit_ref.free_variable_id(),
-            field_maximum: circuit_ref.field_maximum().map(Vec::from),
+            field_maximum: circuit_ref.field_maximum().map(|d| Vec::from(d.bytes())),
             configuration: KeyValueOwned::from_vector(circuit_ref.configuration()),
         }
     }
 }
 
+#[cfg(feature = "bigint")]
+impl CircuitOwned {
+    /// A circuit over the field of the prime `field_prime`, with the instance variables `1..=n`
+    /// set to the given little-endian values. The `free_variable_id` is `n + 1`.
+    /// The values are encoded with the byte width of the field, so empty values are zeros.
+    ///
+    /// # Panics
+    /// If `field_prime` is less than 2, or if a value is not less than `field_prime`.
+    /// See `try_new` to get an error instead.
+    pub fn new(field_prime: &num_bigint::BigUint, instance_values: Vec<Vec<u8>>) -> CircuitOwned {
+        match CircuitOwned::try_new(field_prime, instance_values) {
+            Ok(circuit) => circuit,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Like `new`, but a value not less than `field_prime` is a `FieldOverflow` error, and a
+    /// `field_prime` less than 2 is an error.
+    pub fn try_new(field_prime: &num_bigint::BigUint, instance_values: Vec<Vec<u8>>) -> Result<CircuitOwned> {
+        use crate::bigint::{biguint_to_value, field_byte_len, value_to_biguint};
+
+        if *field_prime < 2u32.into() {
+            return Err(format!("the field prime {} is less than 2", field_prime).into());
+        }
+        let field_maximum = biguint_to_value(&(field_prime - 1u32), 1);
+        let width = field_byte_len(&field_maximum);
+        let values = instance_values.into_iter().enumerate()
+            .map(|(i, value)| {
+                let id = i as u64 + 1;
+                let n = value_to_biguint(&value);
+                if n >= *field_prime {
+                    return Err(ZkiError::FieldOverflow { id: Some(id), value: Some(value) });
+                }
+                Ok((id, biguint_to_value(&n, width)))
+            })
+            .collect::<Result<crate::owned::constraints::LinComb>>()?;
+
+        Ok(CircuitOwned {
+            connections: VariablesOwned::from(&values),
+            free_variable_id: values.len() as u64 + 1,
+            field_maximum: Some(field_maximum),
+            configuration: None,
+        })
+    }
+}
+
 impl CircuitOwned {
     pub fn simple_inputs(num_inputs: u64) -> CircuitOwned {
         let first_input_id = 1;
@@ -70,6 +124,93 @@ impl CircuitOwned {
         }
     }
 
+    /// The boundary of the variables of the circuit: all allocated IDs are below it.
+    pub fn free_variable_id(&self) -> u64 {
+        self.free_variable_id
+    }
+
+    /// Whether a variable is an instance variable, i.e. one of the connections.
+    /// The constant one (ID 0) is not an instance variable.
+    pub fn is_instance(&self, id: u64) -> bool {
+        id != 0 && self.connections.variable_ids.contains(&id)
+    }
+
+    /// The values of the instance variables, by ID.
+    /// Empty if the connections have no values.
+    pub fn instance_assignment(&self) -> BTreeMap<u64, Vec<u8>> {
+        self.connections.get_variables().iter()
+            .filter(|var| var.has_value())
+            .map(|var| (var.id, var.value.to_vec()))
+            .collect()
+    }
+
+    /// Whether a variable is a witness variable, i.e. allocated but not a connection.
+    pub fn is_witness(&self, id: u64) -> bool {
+        id != 0 && id < self.free_variable_id && !self.is_instance(id)
+    }
+
+    /// Cheap sanity checks of the field: `field_maximum` is present and nonzero, the modulus
+    /// `field_maximum + 1` is odd unless it is 2, and the instance values are at most
+    /// `field_maximum`. This is not a primality test.
+    /// The errors are `MissingField`, `FieldMaximumZero`, `EvenModulus`, or `FieldOverflow` with
+    /// the first offending instance variable and its value.
+    pub fn validate_field(&self) -> Result<()> {
+        let endianness = self.value_endianness()?;
+        let field_maximum = self.field_maximum.as_ref()
+            .ok_or_else(|| ZkiError::MissingField { name: "field_maximum".to_string() })?;
+        let maximum = Uint::from_le_bytes(&endianness.to_little_endian(field_maximum));
+
+        if maximum.is_zero() {
+            return Err(ZkiError::FieldMaximumZero { field_maximum: field_maximum.clone() });
+        }
+        // An odd maximum means an even modulus, which is prime only if it is 2.
+        let maximum_is_odd = maximum.to_le_bytes(1)[0] & 1 != 0;
+        if maximum_is_odd && maximum != Uint::one() {
+            return Err(ZkiError::EvenModulus { field_maximum: field_maximum.clone() });
+        }
+        for var in self.connections.get_variables() {
+            let value = Uint::from_le_bytes(&endianness.to_little_endian(var.value));
+            if value > maximum {
+                return Err(ZkiError::FieldOverflow { id: Some(var.id), value: Some(var.value.to_vec()) });
+            }
+        }
+        Ok(())
+    }
+
+    /// The byte order of all field elements of the statement, given by the configuration key
+    /// `value_endianness` with the text "little" or "big". Defaults to little-endian.
+    pub fn value_endianness(&self) -> Result<Endianness> {
+        match self.get_config("value_endianness") {
+            None => Ok(Endianness::Little),
+            Some(kv) => Endianness::parse(kv.text.as_deref().unwrap_or("")),
+        }
+    }
+
+    /// The first configuration entry with the given key.
+    pub fn get_config(&self, key: &str) -> Option<&KeyValueOwned> {
+        self.configuration.iter().flatten().find(|kv| kv.key == key)
+    }
+
+    /// The text of a configuration entry, if it has one.
+    pub fn get_config_string(&self, key: &str) -> Option<&str> {
+        self.get_config(key)?.text.as_deref()
+    }
+
+    /// The number of a configuration entry, if it holds a non-negative number
+    /// and neither text nor data.
+    pub fn get_config_u64(&self, key: &str) -> Option<u64> {
+        let kv = self.get_config(key)?;
+        if kv.text.is_some() || kv.data.is_some() || kv.number < 0 {
+            return None;
+        }
+        Some(kv.number as u64)
+    }
+
+    /// The data of a configuration entry, if it has some.
+    pub fn get_config_bytes(&self, key: &str) -> Option<&[u8]> {
+        self.get_config(key)?.data.as_deref()
+    }
+
     /// Add this structure into a Flatbuffers message builder.
     pub fn build<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         &'args self,
@@ -106,6 +247,7 @@ impl CircuitOwned {
     /// circuit.write_into(&mut buf).unwrap();
     /// assert!(buf.len() > 0);
     /// ```
+    #[cfg(feature = "std")]
     pub fn write_into(&self, writer: &mut impl Write) -> Result<()> {
         let mut builder = FlatBufferBuilder::new();
         let message = self.build(&mut builder);
@@ -113,6 +255,26 @@ impl CircuitOwned {
         writer.write_all(builder.finished_data())?;
         Ok(())
     }
+
+    /// Check that this circuit reads back as written by `write_into`.
+    #[cfg(feature = "std")]
+    pub fn roundtrip_check(&self) -> Result<()> {
+        MessageOwned::Circuit(self.clone()).roundtrip_check()
+    }
+
+    /// Writes this circuit in a canonical form, so that equivalent circuits produce the same bytes.
+    ///
+    /// The connections are sorted by ID with their values, and the configuration is sorted by key,
+    /// keeping the order of entries with the same key.
+    #[cfg(feature = "std")]
+    pub fn write_canonical(&self, writer: &mut impl Write) -> Result<()> {
+        let mut canonical = self.clone();
+        canonical.connections.sort_by_id();
+        if let Some(ref mut configuration) = canonical.configuration {
+            configuration.sort_by(|a, b| a.key.cmp(&b.key));
+        }
+        canonical.write_into(writer)
+    }
 }
 
 #[test]
@@ -149,4 +311,181 @@ fn test_circuit_owned() {
 
     let circuit2 = CircuitOwned::from(circuit_ref);
     assert_eq!(circuit2, circuit);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_is_instance() {
+    // Connections 1, 2, 3; witness 4, 5; free 6.
+    let circuit = crate::examples::example_circuit();
+    assert_eq!(circuit.free_variable_id(), 6);
+
+    assert!(!circuit.is_instance(0));
+    assert!(!circuit.is_witness(0));
+    assert!(circuit.is_instance(1));
+    assert!(circuit.is_instance(3));
+    assert!(!circuit.is_witness(3));
+    assert!(!circuit.is_instance(4));
+    assert!(circuit.is_witness(4));
+    assert!(circuit.is_witness(5));
+    assert!(!circuit.is_instance(6));
+    assert!(!circuit.is_witness(6));
+}
+
+#[test]
+fn test_get_config() {
+    let kv = |key: &str, text: Option<&str>, data: Option<Vec<u8>>, number: i64| KeyValueOwned {
+        key: key.to_string(),
+        text: text.map(String::from),
+        data,
+        number,
+    };
+    let mut circuit = crate::examples::example_circuit();
+    assert_eq!(circuit.get_config("gadget_name"), None);
+
+    circuit.configuration = Some(vec![
+        kv("gadget_name", Some("sha256"), None, 0),
+        kv("rounds", None, None, 64),
+        kv("negative", None, None, -1),
+        kv("seed", None, Some(vec![1, 2]), 0),
+        kv("gadget_name", Some("ignored"), None, 0),
+    ]);
+
+    assert_eq!(circuit.get_config("rounds").unwrap().number, 64);
+    assert_eq!(circuit.get_config("absent"), None);
+
+    assert_eq!(circuit.get_config_string("gadget_name"), Some("sha256"));
+    assert_eq!(circuit.get_config_u64("rounds"), Some(64));
+    assert_eq!(circuit.get_config_bytes("seed"), Some(&[1u8, 2][..]));
+
+    // Type mismatches.
+    assert_eq!(circuit.get_config_string("rounds"), None);
+    assert_eq!(circuit.get_config_u64("gadget_name"), None);
+    assert_eq!(circuit.get_config_u64("negative"), None);
+    assert_eq!(circuit.get_config_bytes("gadget_name"), None);
+    assert_eq!(circuit.get_config_u64("absent"), None);
+}
+
+#[test]
+fn test_write_canonical() {
+    let kv = |key: &str, number: i64| KeyValueOwned { key: key.to_string(), text: None, data: None, number };
+
+    let circuit = CircuitOwned {
+        connections: VariablesOwned {
+            variable_ids: vec![3, 1, 2],
+            values: Some(vec![30, 10, 20]),
+        },
+        free_variable_id: 4,
+        field_maximum: Some(vec![100]),
+        configuration: Some(vec![kv("b", 1), kv("a", 2), kv("b", 3)]),
+    };
+    let reordered = CircuitOwned {
+        connections: VariablesOwned {
+            variable_ids: vec![1, 2, 3],
+            values: Some(vec![10, 20, 30]),
+        },
+        configuration: Some(vec![kv("a", 2), kv("b", 1), kv("b", 3)]),
+        ..circuit.clone()
+    };
+
+    let mut buf1 = Vec::<u8>::new();
+    circuit.write_canonical(&mut buf1).unwrap();
+    let mut buf2 = Vec::<u8>::new();
+    circuit.clone().write_canonical(&mut buf2).unwrap();
+    let mut buf3 = Vec::<u8>::new();
+    reordered.write_canonical(&mut buf3).unwrap();
+    assert_eq!(buf1, buf2);
+    assert_eq!(buf1, buf3);
+
+    let mut plain = Vec::<u8>::new();
+    reordered.write_into(&mut plain).unwrap();
+    assert_eq!(buf1, plain);
+}
+
+#[test]
+fn test_instance_assignment() {
+    let circuit = CircuitOwned {
+        connections: VariablesOwned {
+            variable_ids: vec![3, 1, 2],
+            values: Some(vec![30, 0, 10, 0, 20, 0]),
+        },
+        ..CircuitOwned::default()
+    };
+    let assignment = circuit.instance_assignment();
+    assert_eq!(assignment.into_iter().collect::<Vec<_>>(), vec![
+        (1, vec![10, 0]),
+        (2, vec![20, 0]),
+        (3, vec![30, 0]),
+    ]);
+
+    let without_values = CircuitOwned::simple_inputs(3);
+    assert!(without_values.instance_assignment().is_empty());
+}
+
+#[test]
+fn test_validate_field() {
+    let circuit = crate::examples::example_circuit();
+    circuit.validate_field().unwrap();
+
+    let mut over_range = circuit.clone();
+    over_range.connections.values = Some(crate::examples::serialize_small(&[3u32, 101, 25]));
+    let err = over_range.validate_field().unwrap_err();
+    assert!(matches!(err, ZkiError::FieldOverflow { id: Some(2), value: Some(ref value) } if value == &[101, 0, 0, 0]));
+
+    let mut at_maximum = circuit.clone();
+    at_maximum.connections.values = Some(crate::examples::serialize_small(&[3u32, 100, 25]));
+    at_maximum.validate_field().unwrap();
+
+    let mut zero = circuit.clone();
+    zero.field_maximum = Some(vec![0, 0]);
+    assert_eq!(zero.validate_field().unwrap_err().to_string(), "field_maximum, with bytes 0000, is zero");
+
+    let mut even_modulus = circuit.clone();
+    even_modulus.field_maximum = Some(vec![99]);
+    assert_eq!(even_modulus.validate_field().unwrap_err().to_string(),
+               "field_maximum, with bytes 63, is odd, so the modulus is even and not prime");
+
+    let mut binary = circuit.clone();
+    binary.field_maximum = Some(vec![1]);
+    binary.connections.values = None;
+    binary.validate_field().unwrap();
+
+    let mut missing = circuit;
+    missing.field_maximum = None;
+    assert!(missing.validate_field().is_err());
+}
+
+#[cfg(feature = "bigint")]
+#[test]
+fn test_circuit_new() {
+    use num_bigint::BigUint;
+
+    let circuit = CircuitOwned::new(&BigUint::from(101u32), vec![vec![3], vec![4], vec![25]]);
+    assert_eq!(circuit, CircuitOwned {
+        connections: VariablesOwned { variable_ids: vec![1, 2, 3], values: Some(vec![3, 4, 25]) },
+        free_variable_id: 4,
+        field_maximum: Some(vec![100]),
+        configuration: None,
+    });
+    circuit.validate_field().unwrap();
+
+    let prime: BigUint = "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        .parse().unwrap();
+    let circuit = CircuitOwned::new(&prime, vec![vec![1, 2], vec![]]);
+    assert_eq!(circuit.connections.variable_ids, vec![1, 2]);
+    let mut values = vec![0; 64];
+    values[..2].copy_from_slice(&[1, 2]);
+    assert_eq!(circuit.connections.values, Some(values));
+    assert_eq!(circuit.free_variable_id, 3);
+    assert_eq!(circuit.field_maximum.as_ref().unwrap().len(), 32);
+    circuit.validate_field().unwrap();
+
+    // Empty values are zeros, not unassigned.
+    let circuit = CircuitOwned::new(&BigUint::from(101u32), vec![vec![], vec![]]);
+    assert_eq!(circuit.connections.values, Some(vec![0, 0]));
+
+    let over_range = std::panic::catch_unwind(|| CircuitOwned::new(&BigUint::from(101u32), vec![vec![101]]));
+    assert!(over_range.is_err());
+    let err = CircuitOwned::try_new(&BigUint::from(101u32), vec![vec![3], vec![101]]).unwrap_err();
+    assert!(matches!(err, ZkiError::FieldOverflow { id: Some(2), value: Some(ref value) } if value == &[101]));
+    assert!(CircuitOwned::try_new(&BigUint::from(1u32), vec![]).is_err());
+}
@@ -1,6 +1,8 @@
 //! Helpers to write messages.
 
+use alloc::vec::Vec;
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
+#[cfg(feature = "std")]
 use std::io::Write;
 use serde::{Deserialize, Serialize};
 use crate::zkinterface_generated::zkinterface::{
@@ -10,6 +12,10 @@ use crate::zkinterface_generated::zkinterface::{
     Root,
     RootArgs,
 };
+use super::keyvalue::KeyValueOwned;
+#[cfg(feature = "std")]
+use super::message::MessageOwned;
+#[cfg(feature = "std")]
 use crate::Result;
 
 
@@ -17,7 +23,7 @@ use crate::Result;
 pub struct CommandOwned {
     pub constraints_generation: bool,
     pub witness_generation: bool,
-    //pub parameters: Option<Vec<KeyValue>>,
+    pub parameters: Option<Vec<KeyValueOwned>>,
 }
 
 impl<'a> From<Command<'a>> for CommandOwned {
@@ -26,6 +32,7 @@ impl<'a> From<Command<'a>> for CommandOwned {
         CommandOwned {
             constraints_generation: command_ref.constraints_generation(),
             witness_generation: command_ref.witness_generation(),
+            parameters: KeyValueOwned::from_vector(command_ref.parameters()),
         }
     }
 }
@@ -37,10 +44,13 @@ impl CommandOwned {
         builder: &'mut_bldr mut FlatBufferBuilder<'bldr>,
     ) -> WIPOffset<Root<'bldr>>
     {
+        let parameters = self.parameters.as_ref().map(|params|
+            KeyValueOwned::build_vector(params, builder));
+
         let call = Command::create(builder, &CommandArgs {
             constraints_generation: self.constraints_generation,
             witness_generation: self.witness_generation,
-            parameters: None,
+            parameters,
         });
 
         Root::create(builder, &RootArgs {
@@ -58,6 +68,7 @@ impl CommandOwned {
     /// command.write_into(&mut buf).unwrap();
     /// assert!(buf.len() > 0);
     /// ```
+    #[cfg(feature = "std")]
     pub fn write_into(&self, writer: &mut impl Write) -> Result<()> {
         let mut builder = FlatBufferBuilder::new();
         let message = self.build(&mut builder);
@@ -65,4 +76,59 @@ impl CommandOwned {
         writer.write_all(builder.finished_data())?;
         Ok(())
     }
+
+    /// Check that this command reads back as written by `write_into`.
+    #[cfg(feature = "std")]
+    pub fn roundtrip_check(&self) -> Result<()> {
+        MessageOwned::Command(self.clone()).roundtrip_check()
+    }
+
+    /// Writes this command in a canonical form, with the parameters sorted by key,
+    /// keeping the order of entries with the same key.
+    #[cfg(feature = "std")]
+    pub fn write_canonical(&self, writer: &mut impl Write) -> Result<()> {
+        let mut canonical = self.clone();
+        if let Some(ref mut parameters) = canonical.parameters {
+            parameters.sort_by(|a, b| a.key.cmp(&b.key));
+        }
+        canonical.write_into(writer)
+    }
+}
+
+#[test]
+fn test_command_owned() {
+    use crate::MessageReader;
+
+    let command = CommandOwned {
+        constraints_generation: true,
+        witness_generation: false,
+        parameters: Some(vec![
+            KeyValueOwned {
+                key: "gadget".to_string(),
+                text: Some("sha256".to_string()),
+                data: None,
+                number: 0,
+            },
+            KeyValueOwned {
+                key: "rounds".to_string(),
+                text: None,
+                data: None,
+                number: 64,
+            },
+        ]),
+    };
+
+    let mut buf = Vec::<u8>::new();
+    command.write_into(&mut buf).unwrap();
+    let command2 = CommandOwned {
+        constraints_generation: false,
+        witness_generation: true,
+        parameters: None,
+    };
+    command2.write_into(&mut buf).unwrap();
+
+    let mut reader = MessageReader::new(&buf[..]);
+    assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::Command(command)));
+    assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::Command(command2)));
+    assert_eq!(reader.next_message().unwrap(), None);
 }
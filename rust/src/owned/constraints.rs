@@ -1,7 +1,16 @@
-use std::io::Write;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
+#[cfg(feature = "std")]
+use std::io::Write;
 use serde::{Deserialize, Serialize};
 use crate::{Result, VariablesOwned};
+#[cfg(feature = "std")]
+use super::message::MessageOwned;
+use crate::field::{Field, Uint};
 use crate::zkinterface_generated::zkinterface::{BilinearConstraint, BilinearConstraintArgs, ConstraintSystem, ConstraintSystemArgs, Message, Root, RootArgs};
 
 #[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize)]
@@ -16,6 +25,20 @@ pub struct BilinearConstraintOwned {
     pub linear_combination_c: VariablesOwned,
 }
 
+/// A linear combination as a list of (variable ID, coefficient).
+pub type LinComb = Vec<(u64, Vec<u8>)>;
+
+/// A sparse matrix in coordinate format, as a list of (row, column, coefficient).
+pub type Triplets = Vec<(usize, u64, Vec<u8>)>;
+
+/// A constraint `a * b = c` with its linear combinations as lists of terms.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct ConstraintOwned {
+    pub a: LinComb,
+    pub b: LinComb,
+    pub c: LinComb,
+}
+
 impl<'a> From<ConstraintSystem<'a>> for ConstraintSystemOwned {
     /// Convert from Flatbuffers references to owned structure.
     fn from(constraints_ref: ConstraintSystem) -> ConstraintSystemOwned {
@@ -80,6 +103,85 @@ impl From<&[((Vec<u64>, Vec<u8>), (Vec<u64>, Vec<u8>), (Vec<u64>, Vec<u8>))]> fo
     }
 }
 
+impl From<&VariablesOwned> for LinComb {
+    /// Extract the terms of a linear combination.
+    fn from(lc: &VariablesOwned) -> LinComb {
+        lc.get_variables().iter()
+            .map(|term| (term.id, term.value.to_vec()))
+            .collect()
+    }
+}
+
+impl From<&LinComb> for VariablesOwned {
+    /// Creates a linear combination from its terms. The coefficients are padded to the same length.
+    fn from(terms: &LinComb) -> VariablesOwned {
+        let stride = terms.iter().map(|(_, coeff)| coeff.len()).max().unwrap_or(0);
+        let mut values = Vec::with_capacity(stride * terms.len());
+        for (_, coeff) in terms {
+            values.extend_from_slice(coeff);
+            values.resize(values.len() + stride - coeff.len(), 0);
+        }
+        VariablesOwned {
+            variable_ids: terms.iter().map(|(id, _)| *id).collect(),
+            values: Some(values),
+        }
+    }
+}
+
+impl From<&BilinearConstraintOwned> for ConstraintOwned {
+    fn from(constraint: &BilinearConstraintOwned) -> ConstraintOwned {
+        ConstraintOwned {
+            a: LinComb::from(&constraint.linear_combination_a),
+            b: LinComb::from(&constraint.linear_combination_b),
+            c: LinComb::from(&constraint.linear_combination_c),
+        }
+    }
+}
+
+impl From<&ConstraintOwned> for BilinearConstraintOwned {
+    fn from(constraint: &ConstraintOwned) -> BilinearConstraintOwned {
+        BilinearConstraintOwned {
+            linear_combination_a: VariablesOwned::from(&constraint.a),
+            linear_combination_b: VariablesOwned::from(&constraint.b),
+            linear_combination_c: VariablesOwned::from(&constraint.c),
+        }
+    }
+}
+
+impl ConstraintOwned {
+    /// Sort each linear combination by variable ID, and merge the terms of the same variable
+    /// by adding their coefficients modulo `field_maximum + 1`. Terms that sum to zero are
+    /// dropped. Coefficients are encoded with the width of `field_maximum`.
+    pub fn canonicalize(&mut self, field_maximum: &[u8]) {
+        let field = Field::from_maximum(field_maximum);
+        for lc in [&mut self.a, &mut self.b, &mut self.c] {
+            *lc = canonical_lincomb(lc, &field);
+        }
+    }
+}
+
+fn canonical_lincomb(lc: &LinComb, field: &Field) -> LinComb {
+    let mut sums = BTreeMap::<u64, Uint>::new();
+    for (id, coeff) in lc {
+        let sum = sums.entry(*id).or_default();
+        *sum = field.add(sum, &Uint::from_le_bytes(coeff));
+    }
+    sums.into_iter()
+        .filter(|(_, coeff)| !coeff.is_zero())
+        .map(|(id, coeff)| (id, coeff.to_le_bytes(field.byte_width())))
+        .collect()
+}
+
+impl FromIterator<ConstraintOwned> for ConstraintSystemOwned {
+    fn from_iter<I: IntoIterator<Item=ConstraintOwned>>(constraints: I) -> ConstraintSystemOwned {
+        ConstraintSystemOwned {
+            constraints: constraints.into_iter()
+                .map(|constraint| BilinearConstraintOwned::from(&constraint))
+                .collect(),
+        }
+    }
+}
+
 impl BilinearConstraintOwned {
     /// Add this structure into a Flatbuffers message builder.
     pub fn build<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
@@ -123,6 +225,125 @@ impl ConstraintSystemOwned {
         })
     }
 
+    /// Iterate over the constraints, with their linear combinations as lists of terms.
+    ///
+    /// # Examples
+    /// ```
+    /// let cs = zkinterface::examples::example_constraints();
+    /// let constraint = cs.iter_constraints().last().unwrap();
+    /// assert_eq!(constraint.b, vec![(4, vec![1]), (5, vec![1])]);
+    /// ```
+    pub fn iter_constraints(&self) -> impl Iterator<Item=ConstraintOwned> + '_ {
+        self.constraints.iter().map(ConstraintOwned::from)
+    }
+
+    /// Canonicalize all constraints, see `ConstraintOwned::canonicalize`.
+    /// This makes constraint systems from different sources directly comparable.
+    pub fn canonicalize(&mut self, field_maximum: &[u8]) {
+        for constraint in &mut self.constraints {
+            let mut terms = ConstraintOwned::from(&*constraint);
+            terms.canonicalize(field_maximum);
+            *constraint = BilinearConstraintOwned::from(&terms);
+        }
+    }
+
+    /// The matrices A, B, and C of the system as triplets, where the row is the index of the
+    /// constraint and the column is the variable ID. Empty linear combinations have no triplets.
+    pub fn to_matrices(&self) -> (Triplets, Triplets, Triplets) {
+        let (mut a, mut b, mut c) = (vec![], vec![], vec![]);
+        for (row, constraint) in self.iter_constraints().enumerate() {
+            a.extend(constraint.a.into_iter().map(|(col, coeff)| (row, col, coeff)));
+            b.extend(constraint.b.into_iter().map(|(col, coeff)| (row, col, coeff)));
+            c.extend(constraint.c.into_iter().map(|(col, coeff)| (row, col, coeff)));
+        }
+        (a, b, c)
+    }
+
+    /// Merge constraint systems into one, shifting all variable IDs of `systems[i]` by `id_offsets[i]`.
+    ///
+    /// If `reserved_below` is given, IDs below it are shared between the systems and left
+    /// untouched. For instance, `Some(1)` keeps the constant one at ID 0.
+    ///
+    /// Fails if a shifted ID overflows, or if the shifted IDs of two systems overlap, which would
+    /// alias their variables.
+    ///
+    /// # Examples
+    /// ```
+    /// use zkinterface::{ConstraintSystemOwned, VariablesOwned};
+    ///
+    /// let cs = ConstraintSystemOwned::from(&[
+    ///     ((vec![0], vec![1]), (vec![1], vec![1]), (vec![2], vec![1])),
+    /// ][..]);
+    ///
+    /// let merged = ConstraintSystemOwned::merge(&[cs.clone(), cs], &[0, 10], Some(1)).unwrap();
+    /// assert_eq!(merged.constraints.len(), 2);
+    /// assert_eq!(merged.constraints[1].linear_combination_a.variable_ids, vec![0]);
+    /// assert_eq!(merged.constraints[1].linear_combination_c.variable_ids, vec![12]);
+    /// ```
+    pub fn merge(systems: &[ConstraintSystemOwned], id_offsets: &[u64], reserved_below: Option<u64>) -> Result<ConstraintSystemOwned> {
+        if systems.len() != id_offsets.len() {
+            return Err(format!("cannot merge {} constraint systems with {} ID offsets", systems.len(), id_offsets.len()).into());
+        }
+
+        let is_reserved = |id: u64| matches!(reserved_below, Some(boundary) if id < boundary);
+
+        // The range of shifted IDs of each system, which must not overlap.
+        let mut ranges = vec![];
+        for (i, (system, &offset)) in systems.iter().zip(id_offsets).enumerate() {
+            let mut range: Option<(u64, u64)> = None;
+            for constraint in &system.constraints {
+                for lc in &[
+                    &constraint.linear_combination_a,
+                    &constraint.linear_combination_b,
+                    &constraint.linear_combination_c,
+                ] {
+                    for &id in lc.variable_ids.iter().filter(|&&id| !is_reserved(id)) {
+                        let shifted = id.checked_add(offset).ok_or_else(|| format!(
+                            "the ID {} of constraint system {} overflows with the offset {}", id, i, offset))?;
+                        range = Some(match range {
+                            Some((min, max)) => (min.min(shifted), max.max(shifted)),
+                            None => (shifted, shifted),
+                        });
+                    }
+                }
+            }
+            if let Some((min, max)) = range {
+                ranges.push((min, max, i));
+            }
+        }
+        ranges.sort_unstable();
+        for pair in ranges.windows(2) {
+            if pair[1].0 <= pair[0].1 {
+                let (first, second) = if pair[0].2 < pair[1].2 { (pair[0], pair[1]) } else { (pair[1], pair[0]) };
+                return Err(format!(
+                    "the shifted IDs of constraint systems {} and {} overlap: {}..={} and {}..={}",
+                    first.2, second.2, first.0, first.1, second.0, second.1,
+                ).into());
+            }
+        }
+
+        let shift = |lc: &VariablesOwned, offset: u64| -> VariablesOwned {
+            VariablesOwned {
+                variable_ids: lc.variable_ids.iter()
+                    .map(|&id| if is_reserved(id) { id } else { id + offset })
+                    .collect(),
+                values: lc.values.clone(),
+            }
+        };
+
+        let mut merged = ConstraintSystemOwned::default();
+        for (system, &offset) in systems.iter().zip(id_offsets) {
+            for constraint in &system.constraints {
+                merged.constraints.push(BilinearConstraintOwned {
+                    linear_combination_a: shift(&constraint.linear_combination_a, offset),
+                    linear_combination_b: shift(&constraint.linear_combination_b, offset),
+                    linear_combination_c: shift(&constraint.linear_combination_c, offset),
+                });
+            }
+        }
+        Ok(merged)
+    }
+
     /// Writes this constraint system as a Flatbuffers message into the provided buffer.
     ///
     /// # Examples
@@ -132,6 +353,7 @@ impl ConstraintSystemOwned {
     /// constraints.write_into(&mut buf).unwrap();
     /// assert!(buf.len() > 0);
     /// ```
+    #[cfg(feature = "std")]
     pub fn write_into(&self, writer: &mut impl Write) -> Result<()> {
         let mut builder = FlatBufferBuilder::new();
         let message = self.build(&mut builder);
@@ -139,4 +361,142 @@ impl ConstraintSystemOwned {
         writer.write_all(builder.finished_data())?;
         Ok(())
     }
+
+    /// Check that this constraint system reads back as written by `write_into`.
+    #[cfg(feature = "std")]
+    pub fn roundtrip_check(&self) -> Result<()> {
+        MessageOwned::ConstraintSystem(self.clone()).roundtrip_check()
+    }
+
+    /// Writes this constraint system in a canonical form, with the terms of each linear
+    /// combination sorted by variable ID. The order of the constraints is kept.
+    #[cfg(feature = "std")]
+    pub fn write_canonical(&self, writer: &mut impl Write) -> Result<()> {
+        let mut canonical = self.clone();
+        for constraint in &mut canonical.constraints {
+            constraint.linear_combination_a.sort_by_id();
+            constraint.linear_combination_b.sort_by_id();
+            constraint.linear_combination_c.sort_by_id();
+        }
+        canonical.write_into(writer)
+    }
+}
+
+#[test]
+fn test_merge() {
+    use crate::examples::example_constraints;
+
+    let merged = ConstraintSystemOwned::merge(&[], &[], None).unwrap();
+    assert_eq!(merged, ConstraintSystemOwned::default());
+
+    assert!(ConstraintSystemOwned::merge(&[example_constraints()], &[], None).is_err());
+
+    let cs = example_constraints();
+    let merged = ConstraintSystemOwned::merge(&[cs.clone(), cs.clone()], &[0, 100], None).unwrap();
+    assert_eq!(merged.constraints.len(), 6);
+    assert_eq!(&merged.constraints[..3], &cs.constraints[..]);
+    assert_eq!(merged.constraints[5].linear_combination_a.variable_ids, vec![100]);
+    assert_eq!(merged.constraints[5].linear_combination_b.variable_ids, vec![104, 105]);
+    assert_eq!(merged.constraints[5].linear_combination_b.values, Some(vec![1, 1]));
+
+    // Keep the constant and the inputs 1..4 shared.
+    let merged = ConstraintSystemOwned::merge(&[cs.clone(), cs.clone()], &[0, 100], Some(4)).unwrap();
+    assert_eq!(merged.constraints[3].linear_combination_a.variable_ids, vec![1]);
+    assert_eq!(merged.constraints[3].linear_combination_c.variable_ids, vec![104]);
+    assert_eq!(merged.constraints[5].linear_combination_a.variable_ids, vec![0]);
+    assert_eq!(merged.constraints[5].linear_combination_c.variable_ids, vec![3]);
+
+    // Overlapping offsets: the IDs 0..=5 and 4..=9.
+    let err = ConstraintSystemOwned::merge(&[cs.clone(), cs.clone()], &[4, 0], None).unwrap_err();
+    assert_eq!(err.to_string(), "the shifted IDs of constraint systems 0 and 1 overlap: 4..=9 and 0..=5");
+    // Adjacent ranges do not overlap.
+    ConstraintSystemOwned::merge(&[cs.clone(), cs.clone()], &[0, 6], None).unwrap();
+
+    // Near u64::MAX: the largest ID 5 fits exactly, the next offset overflows.
+    let merged = ConstraintSystemOwned::merge(std::slice::from_ref(&cs), &[u64::MAX - 5], Some(1)).unwrap();
+    assert_eq!(merged.constraints[2].linear_combination_b.variable_ids, vec![u64::MAX - 1, u64::MAX]);
+    assert_eq!(merged.constraints[2].linear_combination_a.variable_ids, vec![0]);
+    let err = ConstraintSystemOwned::merge(&[cs], &[u64::MAX - 4], Some(1)).unwrap_err();
+    assert_eq!(err.to_string(), format!("the ID 5 of constraint system 0 overflows with the offset {}", u64::MAX - 4));
+}
+
+#[test]
+fn test_iter_constraints() {
+    use crate::examples::example_constraints;
+
+    let cs = example_constraints();
+    let constraints: Vec<ConstraintOwned> = cs.iter_constraints().collect();
+    assert_eq!(constraints.len(), 3);
+    assert_eq!(constraints[0], ConstraintOwned {
+        a: vec![(1, vec![1])],
+        b: vec![(1, vec![1])],
+        c: vec![(4, vec![1])],
+    });
+
+    let cs2: ConstraintSystemOwned = constraints.into_iter().collect();
+    assert_eq!(cs2, cs);
+
+    // Coefficients of different lengths.
+    let lc: LinComb = vec![(1, vec![1]), (2, vec![2, 3]), (3, vec![])];
+    let vars = VariablesOwned::from(&lc);
+    assert_eq!(vars.values, Some(vec![1, 0, 2, 3, 0, 0]));
+    assert_eq!(LinComb::from(&vars), vec![(1, vec![1, 0]), (2, vec![2, 3]), (3, vec![0, 0])]);
+}
+
+#[test]
+fn test_write_canonical_constraints() {
+    let cs = ConstraintSystemOwned::from(&[
+        ((vec![2, 1], vec![2, 1]), (vec![0], vec![1]), (vec![3], vec![1])),
+    ][..]);
+    let sorted = ConstraintSystemOwned::from(&[
+        ((vec![1, 2], vec![1, 2]), (vec![0], vec![1]), (vec![3], vec![1])),
+    ][..]);
+    let mut buf1 = Vec::<u8>::new();
+    cs.write_canonical(&mut buf1).unwrap();
+    let mut buf2 = Vec::<u8>::new();
+    sorted.write_into(&mut buf2).unwrap();
+    assert_eq!(buf1, buf2);
+}
+
+#[test]
+fn test_canonicalize() {
+    let mut constraint = ConstraintOwned {
+        // 3*x5 + 2*x1 + 4*x5 = 2*x1 + 7*x5.
+        a: vec![(5, vec![3]), (1, vec![2]), (5, vec![4, 0])],
+        // x2 + 100*x2 = 0 mod 101.
+        b: vec![(2, vec![1]), (3, vec![9]), (2, vec![100])],
+        c: vec![(0, vec![0])],
+    };
+    constraint.canonicalize(&[100]);
+    assert_eq!(constraint, ConstraintOwned {
+        a: vec![(1, vec![2]), (5, vec![7])],
+        b: vec![(3, vec![9])],
+        c: vec![],
+    });
+
+    let mut cs = ConstraintSystemOwned::from(&[
+        ((vec![4, 4], vec![60, 60]), (vec![0], vec![1]), (vec![1], vec![1])),
+    ][..]);
+    cs.canonicalize(&[100]);
+    assert_eq!(cs, ConstraintSystemOwned::from(&[
+        ((vec![4], vec![19]), (vec![0], vec![1]), (vec![1], vec![1])),
+    ][..]));
+}
+
+#[test]
+fn test_to_matrices() {
+    // (x1 + 2*x2) * (x1) = (x3)
+    // (1) * (5) = ()
+    let cs = ConstraintSystemOwned::from(&[
+        ((vec![1, 2], vec![1, 2]), (vec![1], vec![1]), (vec![3], vec![1])),
+        ((vec![0], vec![1]), (vec![0], vec![5]), (vec![], vec![])),
+    ][..]);
+
+    let (a, b, c) = cs.to_matrices();
+    assert_eq!(a, vec![(0, 1, vec![1]), (0, 2, vec![2]), (1, 0, vec![1])]);
+    assert_eq!(b, vec![(0, 1, vec![1]), (1, 0, vec![5])]);
+    assert_eq!(c, vec![(0, 3, vec![1])]);
+
+    let (a, b, c) = ConstraintSystemOwned::default().to_matrices();
+    assert!(a.is_empty() && b.is_empty() && c.is_empty());
 }
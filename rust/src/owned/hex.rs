@@ -0,0 +1,54 @@
+//! Serialize byte fields as hexadecimal strings, for readable JSON.
+//!
+//! Use with `#[serde(with = "hex")]` on fields of type `Option<Vec<u8>>`.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer, Serializer};
+use serde::de::Error;
+
+pub fn encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn decode(text: &str) -> core::result::Result<Vec<u8>, String> {
+    if text.len() & 1 != 0 {
+        return Err(format!("odd length hex string: {:?}", text));
+    }
+    (0..text.len()).step_by(2)
+        .map(|i| {
+            text.get(i..i + 2)
+                .filter(|digits| digits.bytes().all(|c| c.is_ascii_hexdigit()))
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                .ok_or_else(|| format!("invalid hex string: {:?}", text))
+        })
+        .collect()
+}
+
+pub fn serialize<S: Serializer>(bytes: &Option<Vec<u8>>, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+    match bytes {
+        Some(bytes) => serializer.serialize_some(&encode(bytes)),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Option<Vec<u8>>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(text) => decode(&text).map(Some).map_err(D::Error::custom),
+        None => Ok(None),
+    }
+}
+
+
+#[test]
+fn test_hex() {
+    assert_eq!(encode(&[0, 1, 0xab, 0xff]), "0001abff");
+    assert_eq!(decode("0001abff").unwrap(), vec![0, 1, 0xab, 0xff]);
+    assert_eq!(decode("0001ABFF").unwrap(), vec![0, 1, 0xab, 0xff]);
+    assert_eq!(decode("").unwrap(), Vec::<u8>::new());
+    assert!(decode("abc").is_err());
+    assert!(decode("zz").is_err());
+    assert!(decode("+f").is_err());
+    assert!(decode("-1").is_err());
+}
@@ -1,5 +1,8 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
 use serde::{Deserialize, Serialize};
-use std::iter::FromIterator;
 
 use flatbuffers::{FlatBufferBuilder, WIPOffset, Vector, ForwardsUOffset};
 use crate::zkinterface_generated::zkinterface::{
@@ -12,6 +15,7 @@ pub struct KeyValueOwned {
     pub key: String,
     // The value goes into one the following:
     pub text: Option<String>,
+    #[serde(with = "super::hex")]
     pub data: Option<Vec<u8>>,
     pub number: i64,
 }
@@ -22,7 +26,7 @@ impl<'a> From<KeyValue<'a>> for KeyValueOwned {
         KeyValueOwned {
             key: kv_ref.key().unwrap().into(),
             text: kv_ref.text().map(|d| String::from(d)),
-            data: kv_ref.data().map(|d| Vec::from(d)),
+            data: kv_ref.data().map(|d| Vec::from(d.bytes())),
             number: kv_ref.number(),
         }
     }
@@ -1,16 +1,82 @@
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::io::Write;
 
 use crate::reading::Messages;
-use crate::zkinterface_generated::zkinterface::Message;
+#[cfg(feature = "std")]
+use crate::verifier::parse_message;
+use crate::zkinterface_generated::zkinterface::{Message, Root};
+use crate::Result;
 use super::circuit::CircuitOwned;
+use super::command::CommandOwned;
 use super::constraints::ConstraintSystemOwned;
 use super::witness::WitnessOwned;
 
+/// A single message of any type.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum MessageOwned {
+    Circuit(CircuitOwned),
+    ConstraintSystem(ConstraintSystemOwned),
+    Witness(WitnessOwned),
+    Command(CommandOwned),
+}
+
+impl MessageOwned {
+    /// Convert from a Flatbuffers root to owned structure.
+    pub fn from_root(root: Root) -> Result<MessageOwned> {
+        match root.message_type() {
+            Message::Circuit => root.message_as_circuit()
+                .map(|msg| MessageOwned::Circuit(CircuitOwned::from(msg))),
+            Message::ConstraintSystem => root.message_as_constraint_system()
+                .map(|msg| MessageOwned::ConstraintSystem(ConstraintSystemOwned::from(msg))),
+            Message::Witness => root.message_as_witness()
+                .map(|msg| MessageOwned::Witness(WitnessOwned::from(msg))),
+            Message::Command => root.message_as_command()
+                .map(|msg| MessageOwned::Command(CommandOwned::from(msg))),
+            _ => None,
+        }.ok_or_else(|| "empty message".into())
+    }
+
+    /// Writes this message into the provided buffer.
+    #[cfg(feature = "std")]
+    pub fn write_into(&self, writer: &mut impl Write) -> Result<()> {
+        match self {
+            MessageOwned::Circuit(msg) => msg.write_into(writer),
+            MessageOwned::ConstraintSystem(msg) => msg.write_into(writer),
+            MessageOwned::Witness(msg) => msg.write_into(writer),
+            MessageOwned::Command(msg) => msg.write_into(writer),
+        }
+    }
+
+    /// Check that this message reads back as written by `write_into`.
+    #[cfg(feature = "std")]
+    pub fn roundtrip_check(&self) -> Result<()> {
+        let mut buf = Vec::<u8>::new();
+        self.write_into(&mut buf)?;
+        check_read_back(self, &buf)
+    }
+}
+
+/// Check that a buffer is a valid message equal to `expected`.
+#[cfg(feature = "std")]
+fn check_read_back(expected: &MessageOwned, buf: &[u8]) -> Result<()> {
+    let read = MessageOwned::from_root(parse_message(buf)?)?;
+    if read != *expected {
+        return Err(format!("the message does not read back as written: wrote {:?}, read {:?}", expected, read).into());
+    }
+    Ok(())
+}
+
 #[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct MessagesOwned {
     pub circuits: Vec<CircuitOwned>,
     pub constraint_systems: Vec<ConstraintSystemOwned>,
     pub witnesses: Vec<WitnessOwned>,
+    #[serde(default)]
+    pub commands: Vec<CommandOwned>,
 }
 
 impl From<&Messages> for MessagesOwned {
@@ -20,6 +86,7 @@ impl From<&Messages> for MessagesOwned {
             circuits: vec![],
             constraint_systems: vec![],
             witnesses: vec![],
+            commands: vec![],
         };
 
         for msg in messages.into_iter() {
@@ -38,10 +105,100 @@ impl From<&Messages> for MessagesOwned {
                     let witness_ref = msg.message_as_witness().unwrap();
                     owned.witnesses.push(WitnessOwned::from(witness_ref));
                 }
-                Message::Command => {}
-                Message::NONE => {}
+                Message::Command => {
+                    let command_ref = msg.message_as_command().unwrap();
+                    owned.commands.push(CommandOwned::from(command_ref));
+                }
+                _ => {}
             }
         }
         owned
     }
 }
+
+impl MessagesOwned {
+    /// Convert to pretty-printed JSON. Byte fields are written as hexadecimal strings.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse from JSON, as produced by `to_json`.
+    pub fn from_json(json: &str) -> Result<MessagesOwned> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Writes all messages into the provided buffer, in the order circuits, constraints, witnesses,
+    /// commands.
+    #[cfg(feature = "std")]
+    pub fn write_into(&self, writer: &mut impl Write) -> Result<()> {
+        for circuit in &self.circuits {
+            circuit.write_into(writer)?;
+        }
+        for constraints in &self.constraint_systems {
+            constraints.write_into(writer)?;
+        }
+        for witness in &self.witnesses {
+            witness.write_into(writer)?;
+        }
+        for command in &self.commands {
+            command.write_into(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_json_roundtrip() {
+    use crate::examples::*;
+
+    let mut binary = Vec::<u8>::new();
+    example_circuit().write_into(&mut binary).unwrap();
+    write_example_constraints(&mut binary).unwrap();
+    write_example_witness(&mut binary).unwrap();
+    CommandOwned {
+        constraints_generation: true,
+        witness_generation: false,
+        parameters: None,
+    }.write_into(&mut binary).unwrap();
+
+    let mut messages = Messages::new();
+    messages.push_message(binary.clone()).unwrap();
+    let owned = MessagesOwned::from(&messages);
+    assert_eq!(owned.commands.len(), 1);
+
+    let json = owned.to_json().unwrap();
+    assert!(json.contains("\"values\": \"0900000010000000\""));
+    let owned2 = MessagesOwned::from_json(&json).unwrap();
+    assert_eq!(owned2, owned);
+
+    let mut binary2 = Vec::<u8>::new();
+    owned2.write_into(&mut binary2).unwrap();
+    assert_eq!(binary2, binary);
+
+    // JSON without commands, as written before they were included.
+    let json = json.replace("\"commands\"", "\"ignored\"");
+    assert_eq!(MessagesOwned::from_json(&json).unwrap().commands, vec![]);
+}
+
+#[test]
+fn test_roundtrip_check() {
+    use crate::examples::*;
+
+    example_circuit().roundtrip_check().unwrap();
+    example_constraints().roundtrip_check().unwrap();
+    example_witness().roundtrip_check().unwrap();
+    CommandOwned::default().roundtrip_check().unwrap();
+
+    // Change the value of x in the connections.
+    let circuit = MessageOwned::Circuit(example_circuit());
+    let mut buf = Vec::<u8>::new();
+    circuit.write_into(&mut buf).unwrap();
+    let pos = buf.windows(12).position(|w| w == &serialize_small(&[3u32, 4, 25])[..]).unwrap();
+    buf[pos] = 5;
+    let err = check_read_back(&circuit, &buf).unwrap_err();
+    assert!(err.to_string().starts_with("the message does not read back as written"));
+
+    // Corrupt the offset to the message.
+    buf[8] = 0xff;
+    assert!(check_read_back(&circuit, &buf).is_err());
+}
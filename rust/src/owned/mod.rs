@@ -4,4 +4,5 @@ pub mod command;
 pub mod constraints;
 pub mod witness;
 pub mod variables;
-pub mod keyvalue;
\ No newline at end of file
+pub mod keyvalue;
+pub mod hex;
\ No newline at end of file
@@ -1,3 +1,6 @@
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
@@ -5,11 +8,42 @@ use crate::zkinterface_generated::zkinterface::{
     Variables,
     VariablesArgs,
 };
-use crate::reading::{Variable, get_value_size};
+use crate::reading::{Variable, get_value_size, lookup};
+use crate::field::{Field, Uint};
+use crate::{Result, ZkiError};
+
+/// The byte order of values. The canonical order of this crate is little-endian.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
+impl Endianness {
+    /// Parse "little" or "big", as in the configuration key `value_endianness`.
+    pub fn parse(text: &str) -> Result<Endianness> {
+        match text {
+            "little" => Ok(Endianness::Little),
+            "big" => Ok(Endianness::Big),
+            _ => Err(format!("unknown endianness {:?}, expected \"little\" or \"big\"", text).into()),
+        }
+    }
+
+    /// Convert a value in this byte order to little-endian.
+    pub fn to_little_endian(self, value: &[u8]) -> Vec<u8> {
+        let mut value = value.to_vec();
+        if self == Endianness::Big {
+            value.reverse();
+        }
+        value
+    }
+}
 
 #[derive(Clone, Default, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct VariablesOwned {
     pub variable_ids: Vec<u64>,
+    #[serde(with = "super::hex")]
     pub values: Option<Vec<u8>>,
     // pub info: Option<Vec<(String, &'a [u8])>>,
 }
@@ -19,11 +53,11 @@ impl<'a> From<Variables<'a>> for VariablesOwned {
     fn from(variables_ref: Variables) -> VariablesOwned {
         VariablesOwned {
             variable_ids: match variables_ref.variable_ids() {
-                Some(var_ids) => Vec::from(var_ids.safe_slice()),
+                Some(var_ids) => var_ids.iter().collect(),
                 None => vec![],
             },
             values: match variables_ref.values() {
-                Some(bytes) => Some(Vec::from(bytes)),
+                Some(bytes) => Some(Vec::from(bytes.bytes())),
                 None => None,
             },
         }
@@ -49,6 +83,107 @@ impl VariablesOwned {
             ).collect()
     }
 
+    /// Whether the variable IDs are strictly increasing, i.e. sorted and without duplicates.
+    pub fn is_sorted(&self) -> bool {
+        self.variable_ids.windows(2).all(|w| w[0] < w[1])
+    }
+
+    /// Sort the variables by ID, keeping the values in lockstep.
+    /// Fails if an ID appears more than once, leaving the variables sorted with the duplicates.
+    pub fn sort(&mut self) -> Result<()> {
+        self.sort_by_id();
+        match self.variable_ids.windows(2).find(|w| w[0] == w[1]) {
+            Some(w) => Err(format!("variable {} appears more than once", w[0]).into()),
+            None => Ok(()),
+        }
+    }
+
+    /// The value of the variable `id`, if present with a value.
+    /// This is a binary search if the variables are sorted, see `sort`; otherwise a missing
+    /// ID costs a linear search.
+    pub fn get_value(&self, id: u64) -> Option<&[u8]> {
+        let ids = &self.variable_ids;
+        lookup(ids.len(), |i| ids[i], self.values.as_ref()?, id)
+    }
+
+    /// Sort the variables by ID, keeping the values in lockstep. The sort is stable.
+    pub(crate) fn sort_by_id(&mut self) {
+        let stride = match self.values {
+            Some(ref values) => get_value_size(&self.variable_ids, values),
+            None => 0,
+        };
+        let mut order: Vec<usize> = (0..self.variable_ids.len()).collect();
+        order.sort_by_key(|&i| self.variable_ids[i]);
+
+        if let Some(ref values) = self.values {
+            self.values = Some(order.iter()
+                .flat_map(|&i| values[stride * i..stride * (i + 1)].iter().cloned())
+                .collect());
+        }
+        self.variable_ids = order.iter().map(|&i| self.variable_ids[i]).collect();
+    }
+
+    /// Convert all values from the given byte order to little-endian.
+    pub fn to_little_endian(&mut self, endianness: Endianness) {
+        if endianness == Endianness::Little { return; }
+        if let Some(ref mut values) = self.values {
+            let stride = get_value_size(&self.variable_ids, values);
+            if stride == 0 { return; }
+            for value in values.chunks_mut(stride) {
+                value.reverse();
+            }
+        }
+    }
+
+    /// Like `normalize_values`, for values and a `field_maximum` in the given byte order.
+    /// The result is little-endian.
+    pub fn normalize_values_from(&mut self, field_maximum: &[u8], endianness: Endianness) -> Result<()> {
+        self.to_little_endian(endianness);
+        self.normalize_values(&endianness.to_little_endian(field_maximum))
+    }
+
+    /// Reduce all values modulo `field_maximum + 1`, and encode them with the same width,
+    /// which is the number of bytes of `field_maximum`.
+    ///
+    /// Values that exceed twice the modulus are likely corrupted and return an error.
+    /// If there are no values, this does nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// let mut vars = zkinterface::VariablesOwned {
+    ///     variable_ids: vec![1, 2],
+    ///     values: Some(vec![5, 0, 0, 102, 0, 0]),
+    /// };
+    /// vars.normalize_values(&[100]).unwrap();
+    /// assert_eq!(vars.values, Some(vec![5, 1]));
+    /// ```
+    pub fn normalize_values(&mut self, field_maximum: &[u8]) -> Result<()> {
+        let field = Field::from_maximum(field_maximum);
+        let width = field.byte_width();
+        let twice_modulus = field.modulus().add(field.modulus());
+
+        let values = match self.values {
+            Some(ref values) if !values.is_empty() => values,
+            _ => return Ok(()),
+        };
+        let stride = get_value_size(&self.variable_ids, values);
+
+        let mut normalized = Vec::with_capacity(width * self.variable_ids.len());
+        for (i, id) in self.variable_ids.iter().enumerate() {
+            let value = Uint::from_le_bytes(&values[stride * i..stride * (i + 1)]);
+            if value > twice_modulus {
+                return Err(ZkiError::FieldOverflow {
+                    id: Some(*id),
+                    value: Some(values[stride * i..stride * (i + 1)].to_vec()),
+                });
+            }
+            normalized.extend(field.reduce(&value).to_le_bytes(width));
+        }
+
+        self.values = Some(normalized);
+        Ok(())
+    }
+
     /// Add this structure into a Flatbuffers message builder.
     pub fn build<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         &'args self,
@@ -67,3 +202,88 @@ impl VariablesOwned {
         })
     }
 }
+
+#[test]
+fn test_normalize_values() {
+    // Pad short values, reduce over-range values.
+    let mut vars = VariablesOwned {
+        variable_ids: vec![1, 2, 3],
+        values: Some(vec![7, 0, 0, 0, 0xfe, 1]),
+    };
+    let field_maximum = [0xfc, 1]; // 508, 9 bits.
+    vars.normalize_values(&field_maximum).unwrap();
+    assert_eq!(vars.values, Some(vec![7, 0, 0, 0, 1, 0]));
+
+    // Already normalized.
+    let before = vars.clone();
+    vars.normalize_values(&field_maximum).unwrap();
+    assert_eq!(vars, before);
+
+    // Trim to the width of the field.
+    let mut vars = VariablesOwned {
+        variable_ids: vec![1],
+        values: Some(vec![9, 0, 0, 0]),
+    };
+    vars.normalize_values(&[100]).unwrap();
+    assert_eq!(vars.values, Some(vec![9]));
+
+    // 202 = 2 * 101 reduces to 0; 203 is likely a corruption.
+    let mut vars = VariablesOwned {
+        variable_ids: vec![1],
+        values: Some(vec![202]),
+    };
+    vars.normalize_values(&[100]).unwrap();
+    assert_eq!(vars.values, Some(vec![0]));
+    let mut vars = VariablesOwned {
+        variable_ids: vec![1],
+        values: Some(vec![203]),
+    };
+    assert!(vars.normalize_values(&[100]).is_err());
+
+    let mut vars = VariablesOwned::default();
+    vars.normalize_values(&[100]).unwrap();
+    assert_eq!(vars, VariablesOwned::default());
+}
+
+#[test]
+fn test_endianness() {
+    let little = VariablesOwned {
+        variable_ids: vec![1, 2],
+        values: Some(vec![0x34, 0x12, 0, 0, 0xff, 0xff, 0, 0]),
+    };
+    let big = VariablesOwned {
+        variable_ids: vec![1, 2],
+        values: Some(vec![0, 0, 0x12, 0x34, 0, 0, 0xff, 0xff]),
+    };
+    let field_maximum = [0xfe, 0xff, 0xff];
+
+    let mut normalized_little = little.clone();
+    normalized_little.normalize_values_from(&field_maximum, Endianness::Little).unwrap();
+    let mut normalized_big = big.clone();
+    normalized_big.normalize_values_from(&[0xff, 0xff, 0xfe], Endianness::Big).unwrap();
+    assert_eq!(normalized_big, normalized_little);
+    assert_eq!(normalized_little.values, Some(vec![0x34, 0x12, 0, 0xff, 0xff, 0]));
+
+    let mut converted = big.clone();
+    converted.to_little_endian(Endianness::Big);
+    assert_eq!(converted, little);
+
+    assert_eq!(Endianness::default(), Endianness::Little);
+    assert_eq!(Endianness::parse("big").unwrap(), Endianness::Big);
+    assert!(Endianness::parse("middle").is_err());
+}
+
+#[test]
+fn test_sort() {
+    let mut vars = VariablesOwned { variable_ids: vec![5, 2], values: Some(vec![50, 0, 20, 0]) };
+    assert!(!vars.is_sorted());
+    vars.sort().unwrap();
+    assert!(vars.is_sorted());
+    assert_eq!(vars, VariablesOwned { variable_ids: vec![2, 5], values: Some(vec![20, 0, 50, 0]) });
+    assert_eq!(vars.get_value(5), Some(&[50, 0][..]));
+    assert_eq!(vars.get_value(3), None);
+
+    let mut vars = VariablesOwned { variable_ids: vec![3, 1, 3], values: None };
+    assert_eq!(vars.sort().unwrap_err().to_string(), "variable 3 appears more than once");
+    assert!(!vars.is_sorted());
+}
@@ -1,4 +1,9 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::vec;
+use alloc::vec::Vec;
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
+#[cfg(feature = "std")]
 use std::io::Write;
 use serde::{Deserialize, Serialize};
 use crate::zkinterface_generated::zkinterface::{
@@ -8,7 +13,12 @@ use crate::zkinterface_generated::zkinterface::{
     Root,
     RootArgs,
 };
+use super::constraints::ConstraintSystemOwned;
 use super::variables::VariablesOwned;
+use crate::reading::get_value_size;
+use crate::field::Uint;
+#[cfg(feature = "std")]
+use super::message::MessageOwned;
 use crate::Result;
 
 
@@ -27,6 +37,94 @@ impl<'a> From<Witness<'a>> for WitnessOwned {
 }
 
 impl WitnessOwned {
+    /// Whether both witnesses assign the same values to the same variables, in any order.
+    /// Values are compared as numbers, so that trailing zero bytes do not matter.
+    pub fn values_equal(&self, other: &WitnessOwned) -> bool {
+        fn assignment(witness: &WitnessOwned) -> Option<BTreeMap<u64, Uint>> {
+            let vars = &witness.assigned_variables;
+            vars.values.as_ref()?;
+            Some(vars.get_variables().iter()
+                .map(|var| (var.id, Uint::from_le_bytes(var.value)))
+                .collect())
+        }
+
+        match (assignment(self), assignment(other)) {
+            (Some(a), Some(b)) => a == b,
+            (None, None) => {
+                let mut a = self.assigned_variables.variable_ids.clone();
+                let mut b = other.assigned_variables.variable_ids.clone();
+                a.sort_unstable();
+                b.sort_unstable();
+                a == b
+            }
+            _ => false,
+        }
+    }
+
+    /// Split into `n` witnesses of contiguous ranges of variable IDs, with roughly equal counts.
+    ///
+    /// The variables are sorted by ID first, so concatenating the shards in order gives back the
+    /// witness if it was sorted. If `n` exceeds the number of variables, the last shards are empty.
+    ///
+    /// # Panics
+    /// If `n` is zero.
+    pub fn shard(&self, n: usize) -> Vec<WitnessOwned> {
+        assert!(n > 0, "cannot split a witness into zero shards");
+        let mut sorted = self.assigned_variables.clone();
+        sorted.sort_by_id();
+        let stride = match sorted.values {
+            Some(ref values) => get_value_size(&sorted.variable_ids, values),
+            None => 0,
+        };
+
+        let len = sorted.variable_ids.len();
+        let mut start = 0;
+        (0..n).map(|i| {
+            let end = start + len / n + if i < len % n { 1 } else { 0 };
+            let shard = VariablesOwned {
+                variable_ids: sorted.variable_ids[start..end].to_vec(),
+                values: sorted.values.as_ref().map(|values| values[stride * start..stride * end].to_vec()),
+            };
+            start = end;
+            WitnessOwned { assigned_variables: shard }
+        }).collect()
+    }
+
+    /// Remove the variables that do not appear in any constraint.
+    /// The remaining variables and their values keep their order.
+    pub fn restrict_to(&mut self, cs: &ConstraintSystemOwned) {
+        let mut used = BTreeSet::new();
+        for constraint in &cs.constraints {
+            for lc in &[
+                &constraint.linear_combination_a,
+                &constraint.linear_combination_b,
+                &constraint.linear_combination_c,
+            ] {
+                used.extend(lc.variable_ids.iter().cloned());
+            }
+        }
+
+        let vars = &mut self.assigned_variables;
+        let stride = match vars.values {
+            Some(ref values) => get_value_size(&vars.variable_ids, values),
+            None => 0,
+        };
+        let mut kept_ids = vec![];
+        let mut kept_values = vec![];
+        for (i, id) in vars.variable_ids.iter().enumerate() {
+            if !used.contains(id) { continue; }
+            kept_ids.push(*id);
+            if let Some(ref values) = vars.values {
+                kept_values.extend_from_slice(&values[stride * i..stride * (i + 1)]);
+            }
+        }
+
+        vars.variable_ids = kept_ids;
+        if vars.values.is_some() {
+            vars.values = Some(kept_values);
+        }
+    }
+
     /// Add this structure into a Flatbuffers message builder.
     pub fn build<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
         &'args self,
@@ -54,6 +152,7 @@ impl WitnessOwned {
     /// witness.write_into(&mut buf).unwrap();
     /// assert!(buf.len() > 0);
     /// ```
+    #[cfg(feature = "std")]
     pub fn write_into(&self, writer: &mut impl Write) -> Result<()> {
         let mut builder = FlatBufferBuilder::new();
         let message = self.build(&mut builder);
@@ -61,4 +160,268 @@ impl WitnessOwned {
         writer.write_all(builder.finished_data())?;
         Ok(())
     }
+
+    /// Check that this witness reads back as written by `write_into`.
+    #[cfg(feature = "std")]
+    pub fn roundtrip_check(&self) -> Result<()> {
+        MessageOwned::Witness(self.clone()).roundtrip_check()
+    }
+
+    /// Writes this witness in a canonical form, with the variables sorted by ID.
+    #[cfg(feature = "std")]
+    pub fn write_canonical(&self, writer: &mut impl Write) -> Result<()> {
+        let mut canonical = self.clone();
+        canonical.assigned_variables.sort_by_id();
+        canonical.write_into(writer)
+    }
+
+    /// Encode in a compact format for internal caches. This is not the zkInterface format and is
+    /// not meant to be exchanged; use `write_into` for that.
+    ///
+    /// The layout is a version byte (`COMPACT_VERSION`), the number of variables and each ID as
+    /// LEB128 varints, then a byte 1 followed by the length and bytes of the values, or a byte 0
+    /// if there are no values.
+    pub fn to_compact_bytes(&self) -> Vec<u8> {
+        let vars = &self.assigned_variables;
+        let mut buf = vec![COMPACT_VERSION];
+        write_varint(&mut buf, vars.variable_ids.len() as u64);
+        for &id in &vars.variable_ids {
+            write_varint(&mut buf, id);
+        }
+        match vars.values {
+            Some(ref values) => {
+                buf.push(1);
+                write_varint(&mut buf, values.len() as u64);
+                buf.extend_from_slice(values);
+            }
+            None => buf.push(0),
+        }
+        buf
+    }
+
+    /// Decode the format of `to_compact_bytes`.
+    pub fn from_compact_bytes(buf: &[u8]) -> Result<WitnessOwned> {
+        let mut reader = CompactReader { buf, pos: 0 };
+        let version = reader.byte()?;
+        if version != COMPACT_VERSION {
+            return Err(format!("unknown version {} of the compact witness format", version).into());
+        }
+
+        let count = reader.varint()? as usize;
+        // Each ID takes at least one byte; do not trust the count for allocation.
+        let mut variable_ids = Vec::with_capacity(count.min(buf.len()));
+        for _ in 0..count {
+            variable_ids.push(reader.varint()?);
+        }
+        let values = match reader.byte()? {
+            0 => None,
+            1 => {
+                let len = reader.varint()? as usize;
+                Some(reader.bytes(len)?.to_vec())
+            }
+            flag => return Err(format!("invalid compact witness: unknown values flag {}", flag).into()),
+        };
+        if reader.pos != buf.len() {
+            return Err(format!("invalid compact witness: {} trailing bytes", buf.len() - reader.pos).into());
+        }
+        Ok(WitnessOwned { assigned_variables: VariablesOwned { variable_ids, values } })
+    }
+}
+
+/// The version of the format of `WitnessOwned::to_compact_bytes`.
+pub const COMPACT_VERSION: u8 = 1;
+
+fn write_varint(buf: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        buf.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    buf.push(n as u8);
+}
+
+struct CompactReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CompactReader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.buf.len() - self.pos {
+            return Err("invalid compact witness: truncated".into());
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            if shift == 63 && byte > 1 {
+                break;
+            }
+            n |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err("invalid compact witness: varint overflows 64 bits".into())
+    }
+}
+
+#[test]
+fn test_restrict_to() {
+    let cs = ConstraintSystemOwned::from(&[
+        ((vec![2], vec![1]), (vec![3], vec![1]), (vec![5], vec![1])),
+        ((vec![0], vec![1]), (vec![2], vec![1]), (vec![1], vec![1])),
+    ][..]);
+
+    let mut witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![2, 3, 4, 5, 6],
+            values: Some(vec![12, 0, 13, 0, 14, 0, 15, 0, 16, 0]),
+        },
+    };
+    witness.restrict_to(&cs);
+    assert_eq!(witness.assigned_variables, VariablesOwned {
+        variable_ids: vec![2, 3, 5],
+        values: Some(vec![12, 0, 13, 0, 15, 0]),
+    });
+
+    let mut witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![2, 3, 4, 5, 6],
+            values: None,
+        },
+    };
+    witness.restrict_to(&cs);
+    assert_eq!(witness.assigned_variables.variable_ids, vec![2, 3, 5]);
+    assert_eq!(witness.assigned_variables.values, None);
+}
+
+#[test]
+fn test_values_equal() {
+    let padded = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![4, 5],
+            values: Some([&[9u8, 0, 0, 0][..], &[16, 1, 0, 0]].concat()),
+        },
+    };
+    let trimmed = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![5, 4],
+            values: Some(vec![16, 1, 9, 0]),
+        },
+    };
+    assert_ne!(padded, trimmed);
+    assert!(padded.values_equal(&trimmed));
+    assert!(trimmed.values_equal(&padded));
+
+    let different = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![4, 5],
+            values: Some(vec![9, 16]),
+        },
+    };
+    assert!(!padded.values_equal(&different));
+
+    let fewer = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![4],
+            values: Some(vec![9]),
+        },
+    };
+    assert!(!padded.values_equal(&fewer));
+
+    let no_values = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![4, 5],
+            values: None,
+        },
+    };
+    assert!(!padded.values_equal(&no_values));
+    assert!(no_values.values_equal(&no_values.clone()));
+}
+
+#[test]
+fn test_shard() {
+    let witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![1, 2, 3, 4, 5, 6],
+            values: Some(vec![1, 0, 2, 0, 3, 0, 4, 0, 5, 0, 6, 0]),
+        },
+    };
+    let merge = |shards: &[WitnessOwned]| VariablesOwned {
+        variable_ids: shards.iter().flat_map(|w| w.assigned_variables.variable_ids.clone()).collect(),
+        values: Some(shards.iter().flat_map(|w| w.assigned_variables.values.clone().unwrap()).collect()),
+    };
+
+    // Even.
+    let shards = witness.shard(3);
+    assert_eq!(shards.len(), 3);
+    assert_eq!(shards[1].assigned_variables, VariablesOwned {
+        variable_ids: vec![3, 4],
+        values: Some(vec![3, 0, 4, 0]),
+    });
+    assert_eq!(merge(&shards), witness.assigned_variables);
+
+    // Uneven.
+    let shards = witness.shard(4);
+    let counts: Vec<usize> = shards.iter().map(|w| w.assigned_variables.variable_ids.len()).collect();
+    assert_eq!(counts, vec![2, 2, 1, 1]);
+    assert_eq!(merge(&shards), witness.assigned_variables);
+
+    // More shards than variables.
+    let shards = witness.shard(8);
+    assert_eq!(shards[7].assigned_variables.variable_ids, Vec::<u64>::new());
+    assert_eq!(merge(&shards), witness.assigned_variables);
+
+    // One shard.
+    assert_eq!(witness.shard(1), vec![witness.clone()]);
+
+    // Unsorted.
+    let unsorted = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![5, 2],
+            values: Some(vec![5, 2]),
+        },
+    };
+    let shards = unsorted.shard(2);
+    assert_eq!(shards[0].assigned_variables.values, Some(vec![2]));
+}
+
+#[test]
+fn test_compact_bytes() {
+    use crate::examples::example_witness;
+
+    let witness = example_witness();
+    let buf = witness.to_compact_bytes();
+    assert_eq!(buf[..4], [COMPACT_VERSION, 2, 4, 5]);
+    assert_eq!(WitnessOwned::from_compact_bytes(&buf).unwrap(), witness);
+
+    let mut large = WitnessOwned {
+        assigned_variables: VariablesOwned { variable_ids: vec![u64::MAX, 0, 300], values: None },
+    };
+    assert_eq!(WitnessOwned::from_compact_bytes(&large.to_compact_bytes()).unwrap(), large);
+    large.assigned_variables.values = Some(vec![]);
+    assert_eq!(WitnessOwned::from_compact_bytes(&large.to_compact_bytes()).unwrap(), large);
+
+    let mut unknown = buf.clone();
+    unknown[0] = 2;
+    assert_eq!(WitnessOwned::from_compact_bytes(&unknown).unwrap_err().to_string(),
+               "unknown version 2 of the compact witness format");
+
+    for len in 0..buf.len() {
+        assert!(WitnessOwned::from_compact_bytes(&buf[..len]).is_err());
+    }
+    let mut trailing = buf;
+    trailing.push(0);
+    assert!(WitnessOwned::from_compact_bytes(&trailing).is_err());
+    let overflow = [COMPACT_VERSION, 1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02, 0];
+    assert!(WitnessOwned::from_compact_bytes(&overflow).is_err());
 }
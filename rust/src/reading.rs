@@ -1,33 +1,49 @@
 //! Helpers to read messages.
 
-use flatbuffers::{read_scalar_at, SIZE_UOFFSET, UOffsetT};
-use std::collections::HashMap;
-use std::fmt;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt;
+use flatbuffers::{SIZE_UOFFSET, UOffsetT, Vector};
+#[cfg(feature = "std")]
 use std::fs::File;
-use std::io::Read;
+#[cfg(feature = "std")]
+use std::io::{self, Read};
+#[cfg(feature = "std")]
 use std::path::Path;
 
 use crate::zkinterface_generated::zkinterface::{
     BilinearConstraint,
     Circuit,
-    get_size_prefixed_root_as_root,
     Root,
     Variables,
+    Witness,
 };
+#[cfg(feature = "std")]
+use crate::zkinterface_generated::zkinterface::size_prefixed_root_as_root_unchecked;
+#[cfg(feature = "std")]
+use crate::owned::message::MessageOwned;
+use crate::verifier::parse_message;
+#[cfg(feature = "std")]
+use crate::verifier::verify_size_prefixed_root;
 use crate::Result;
+#[cfg(feature = "std")]
+use crate::ZkiError;
 
 pub fn read_circuit(msg: &[u8]) -> Result<Circuit> {
-    get_size_prefixed_root_as_root(msg)
+    parse_message(msg)?
         .message_as_circuit().ok_or("not a Circuit message".into())
 }
 
 pub fn parse_call(call_msg: &[u8]) -> Option<(Circuit, Vec<Variable>)> {
-    let call = get_size_prefixed_root_as_root(call_msg).message_as_circuit()?;
-    let input_var_ids = call.connections()?.variable_ids()?.safe_slice();
+    let call = parse_message(call_msg).ok()?.message_as_circuit()?;
+    let input_var_ids: Vec<u64> = call.connections()?.variable_ids()?.iter().collect();
 
     let assigned = match call.connections()?.values() {
         Some(bytes) => {
-            let stride = get_value_size(input_var_ids, bytes);
+            let bytes = bytes.bytes();
+            let stride = get_value_size(&input_var_ids, bytes);
 
             (0..input_var_ids.len()).map(|i|
                 Variable {
@@ -53,7 +69,7 @@ pub fn is_contiguous(mut first_id: u64, ids: &[u64]) -> bool {
 // Read a flatbuffers size prefix (4 bytes, little-endian). Size including the prefix.
 pub fn read_size_prefix(buf: &[u8]) -> usize {
     if buf.len() < SIZE_UOFFSET { return 0; }
-    let size = read_scalar_at::<UOffsetT>(buf, 0) as usize;
+    let size = UOffsetT::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as usize;
     SIZE_UOFFSET + size
 }
 
@@ -68,6 +84,7 @@ pub fn split_messages(mut buf: &[u8]) -> Vec<&[u8]> {
     bufs
 }
 
+#[cfg(feature = "std")]
 pub fn read_buffer(stream: &mut impl Read) -> Result<Vec<u8>> {
     let mut buffer = vec![0u8; 4];
     if stream.read_exact(&mut buffer).is_err() {
@@ -84,6 +101,146 @@ pub fn read_buffer(stream: &mut impl Read) -> Result<Vec<u8>> {
     Ok(buffer)
 }
 
+/// Read messages one at a time from a stream, without loading the whole stream in memory.
+///
+/// # Examples
+/// ```
+/// use zkinterface::{MessageReader, MessageOwned, WitnessOwned};
+///
+/// let mut buf = Vec::<u8>::new();
+/// WitnessOwned::default().write_into(&mut buf).unwrap();
+///
+/// let mut reader = MessageReader::new(&buf[..]);
+/// assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::Witness(WitnessOwned::default())));
+/// assert_eq!(reader.next_message().unwrap(), None);
+/// ```
+#[cfg(feature = "std")]
+pub struct MessageReader<'a, R: Read> {
+    reader: R,
+    max_message_size: usize,
+    progress: Option<Box<dyn FnMut(Progress) + 'a>>,
+    read: Progress,
+    reported_bytes: u64,
+}
+
+/// The default limit on the size of a message for `MessageReader`, 1 GiB.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1 << 30;
+
+/// The amount of work done so far, as reported to progress callbacks.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Progress {
+    pub bytes: u64,
+    pub messages: u64,
+    pub constraints: u64,
+}
+
+/// Progress callbacks are called at most once per this many bytes or constraints, and at the end.
+pub const PROGRESS_INTERVAL: u64 = 1 << 16;
+
+#[cfg(feature = "std")]
+impl<'a, R: Read> MessageReader<'a, R> {
+    pub fn new(reader: R) -> MessageReader<'a, R> {
+        MessageReader {
+            reader,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            progress: None,
+            read: Progress::default(),
+            reported_bytes: 0,
+        }
+    }
+
+    /// Reject messages announcing more than `max_message_size` bytes, not counting the size
+    /// prefix. The limit is checked on the prefix, before any memory is allocated.
+    pub fn with_max_message_size(reader: R, max_message_size: usize) -> MessageReader<'a, R> {
+        MessageReader { max_message_size, ..MessageReader::new(reader) }
+    }
+
+    /// Report the bytes and messages read to `callback`, after a message once at least
+    /// `PROGRESS_INTERVAL` bytes were read since the last report, and at the end of the stream.
+    /// The callback may borrow from its environment for the lifetime of the reader.
+    pub fn with_progress(mut self, callback: impl FnMut(Progress) + 'a) -> MessageReader<'a, R> {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    fn report(&mut self, at_end: bool) {
+        if let Some(ref mut callback) = self.progress {
+            let unreported = self.read.bytes - self.reported_bytes;
+            if unreported >= PROGRESS_INTERVAL || (at_end && unreported > 0) {
+                self.reported_bytes = self.read.bytes;
+                callback(self.read);
+            }
+        }
+    }
+
+    /// Read the next size-prefixed message and verify it.
+    /// Returns None at the end of the stream, or at an explicit size 0 as end marker.
+    pub fn next_buffer(&mut self) -> Result<Option<Vec<u8>>> {
+        let buffer = self.read_buffer()?;
+        match buffer {
+            Some(ref buffer) => {
+                self.read.bytes += buffer.len() as u64;
+                self.read.messages += 1;
+                self.report(false);
+            }
+            None => self.report(true),
+        }
+        Ok(buffer)
+    }
+
+    fn read_buffer(&mut self) -> Result<Option<Vec<u8>>> {
+        let mut prefix = [0u8; SIZE_UOFFSET];
+        let mut filled = 0;
+        while filled < SIZE_UOFFSET {
+            match self.reader.read(&mut prefix[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        if filled == 0 {
+            return Ok(None); // End of stream at the correct place.
+        }
+        if filled < SIZE_UOFFSET {
+            return Err(ZkiError::FlatbufferVerification(format!("truncated message: the stream ended within a size prefix ({} of {} bytes)", filled, SIZE_UOFFSET)));
+        }
+
+        let size = read_size_prefix(&prefix);
+        if size <= SIZE_UOFFSET {
+            return Ok(None); // Explicit size 0 as end marker.
+        }
+        if size - SIZE_UOFFSET > self.max_message_size {
+            return Err(ZkiError::MessageTooLarge { size: size - SIZE_UOFFSET, max: self.max_message_size });
+        }
+
+        let mut buffer = prefix.to_vec();
+        buffer.resize(size, 0);
+        if let Err(err) = self.reader.read_exact(&mut buffer[SIZE_UOFFSET..]) {
+            return if err.kind() == io::ErrorKind::UnexpectedEof {
+                Err(ZkiError::FlatbufferVerification(format!("truncated message: the stream ended before the announced {} bytes", size - SIZE_UOFFSET)))
+            } else {
+                Err(err.into())
+            };
+        }
+
+        verify_size_prefixed_root(&buffer)?;
+        Ok(Some(buffer))
+    }
+
+    /// Read and parse the next message. Returns None at the end of the stream.
+    pub fn next_message(&mut self) -> Result<Option<MessageOwned>> {
+        match self.next_buffer()? {
+            Some(buffer) => {
+                // Safe because next_buffer verified the buffer.
+                let root = unsafe { size_prefixed_root_as_root_unchecked(&buffer) };
+                Ok(Some(MessageOwned::from_root(root)?))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
 pub fn get_value_size(var_ids: &[u64], values: &[u8]) -> usize {
     if var_ids.len() == 0 { 0 } else { values.len() / var_ids.len() }
 }
@@ -97,7 +254,7 @@ pub struct Messages {
 
 impl fmt::Debug for Messages {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use crate::zkinterface_generated::zkinterface::Message::*;
+        use crate::zkinterface_generated::zkinterface::Message;
 
         let mut has_circuit = false;
         let mut has_witness = false;
@@ -105,16 +262,15 @@ impl fmt::Debug for Messages {
 
         for root in self.into_iter() {
             match root.message_type() {
-                Circuit => has_circuit = true,
-                Witness => has_witness = true,
-                ConstraintSystem => has_constraints = true,
-                Command => {}
-                NONE => {}
+                Message::Circuit => has_circuit = true,
+                Message::Witness => has_witness = true,
+                Message::ConstraintSystem => has_constraints = true,
+                _ => {}
             }
         }
 
         if has_circuit {
-            write!(f, "\nZkInterface {:?}\n", Circuit)?;
+            write!(f, "\nZkInterface {:?}\n", Message::Circuit)?;
             if let Some(vars) = self.connection_variables() {
                 write!(f, "Public variables:\n")?;
                 for var in vars {
@@ -128,7 +284,7 @@ impl fmt::Debug for Messages {
         }
 
         if has_witness {
-            write!(f, "\nZkInterface {:?}\n", Witness)?;
+            write!(f, "\nZkInterface {:?}\n", Message::Witness)?;
             if let Some(vars) = self.private_variables() {
                 write!(f, "Private variables:\n")?;
                 for var in vars {
@@ -138,7 +294,7 @@ impl fmt::Debug for Messages {
         }
 
         if has_constraints {
-            write!(f, "\nZkInterface {:?}\n", ConstraintSystem)?;
+            write!(f, "\nZkInterface {:?}\n", Message::ConstraintSystem)?;
             for constraint in self.iter_constraints() {
                 write!(f, "{:?}\n", constraint)?;
             }
@@ -166,11 +322,21 @@ impl Messages {
         }
     }
 
+    /// Add a buffer of one or more messages, each verified with `verifier::parse_message`.
+    /// A message of size zero ends the buffer.
     pub fn push_message(&mut self, buf: Vec<u8>) -> Result<()> {
+        let mut rest = &buf[..];
+        loop {
+            let size = read_size_prefix(rest);
+            if size <= SIZE_UOFFSET { break; }
+            parse_message(rest)?;
+            rest = &rest[size..];
+        }
         self.messages.push(buf);
         Ok(())
     }
 
+    #[cfg(feature = "std")]
     pub fn read_from(&mut self, reader: &mut impl Read) -> Result<()> {
         loop {
             let buffer = read_buffer(reader)?;
@@ -181,6 +347,7 @@ impl Messages {
         }
     }
 
+    #[cfg(feature = "std")]
     pub fn read_file(&mut self, path: impl AsRef<Path>) -> Result<()> {
         let mut file = File::open(&path)?;
         let mut buf = Vec::new();
@@ -230,7 +397,7 @@ impl Messages {
             circuit.free_variable_id())?;
 
         // Collect assigned values, if any.
-        let mut values = HashMap::with_capacity(vars.len());
+        let mut values = BTreeMap::new();
 
         for assigned_var in self.iter_witness() {
             values.insert(assigned_var.id, assigned_var.value);
@@ -250,14 +417,14 @@ impl Messages {
 }
 
 pub fn collect_connection_variables<'a>(conn: &Variables<'a>, first_id: u64) -> Option<Vec<Variable<'a>>> {
-    let var_ids = conn.variable_ids()?.safe_slice();
+    let var_ids: Vec<u64> = conn.variable_ids()?.iter().collect();
 
     let values = match conn.values() {
-        Some(values) => values,
+        Some(values) => values.bytes(),
         None => &[], // No values, only variable ids and empty values.
     };
 
-    let stride = get_value_size(var_ids, values);
+    let stride = get_value_size(&var_ids, values);
 
     let vars = (0..var_ids.len())
         .filter(|&i| // Ignore variables below first_id, if any.
@@ -273,7 +440,7 @@ pub fn collect_connection_variables<'a>(conn: &Variables<'a>, first_id: u64) ->
 }
 
 pub fn collect_unassigned_private_variables<'a>(conn: &Variables<'a>, first_id: u64, free_id: u64) -> Option<Vec<Variable<'a>>> {
-    let var_ids = conn.variable_ids()?.safe_slice();
+    let var_ids: Vec<u64> = conn.variable_ids()?.iter().collect();
 
     let vars = (first_id..free_id)
         .filter(|id| // Ignore variables already in the connections.
@@ -334,9 +501,12 @@ impl<'a> Iterator for MessageIterator<'a> {
             // Move to the next message in the current buffer.
             self.offset += size;
 
-            // Parse the current message.
-            let root = get_size_prefixed_root_as_root(&buf[..size]);
-            return Some(root);
+            // Parse the current message. The buffers are public, so verify it again,
+            // and skip it if it is malformed.
+            match parse_message(&buf[..size]) {
+                Ok(root) => return Some(root),
+                Err(_) => continue,
+            }
         }
     }
 }
@@ -398,14 +568,14 @@ impl<'a> Iterator for R1CSIterator<'a> {
 
         fn to_vec<'a>(lc: Variables<'a>) -> Vec<Term<'a>> {
             let mut terms = vec![];
-            let var_ids: &[u64] = lc.variable_ids().unwrap().safe_slice();
-            let values: &[u8] = lc.values().unwrap();
+            let var_ids = lc.variable_ids().unwrap();
+            let values: &[u8] = lc.values().unwrap().bytes();
 
-            let stride = get_value_size(var_ids, values);
+            let stride = if var_ids.is_empty() { 0 } else { values.len() / var_ids.len() };
 
-            for i in 0..var_ids.len() {
+            for (i, id) in var_ids.iter().enumerate() {
                 terms.push(Term {
-                    id: var_ids[i],
+                    id,
                     value: &values[stride * i..stride * (i + 1)],
                 });
             }
@@ -422,13 +592,54 @@ impl<'a> Iterator for R1CSIterator<'a> {
     // TODO: Replace unwrap and panic with Result.
 }
 
+/// Find the value of a variable in a witness, without copying.
+///
+/// The lookup is a binary search if the variable IDs of the witness are sorted in increasing
+/// order, see `lookup`.
+/// Returns None if the variable is not in the witness or has no value.
+pub fn witness_value<'a>(witness: Witness<'a>, id: u64) -> Option<&'a [u8]> {
+    let vars = witness.assigned_variables()?;
+    let var_ids = vars.variable_ids()?;
+    lookup(var_ids.len(), |i| var_ids.get(i), vars.values()?.bytes(), id)
+}
+
+/// Find a value by ID among `count` IDs, where `id_at(i)` is the ID at index `i`.
+/// This is a binary search, so it is fast if the IDs are sorted.
+/// If the ID is not found, a linear search follows, so that the result is correct for unsorted
+/// IDs too; a missing ID costs a scan of all IDs.
+pub(crate) fn lookup<'a>(count: usize, id_at: impl Fn(usize) -> u64, values: &'a [u8], id: u64) -> Option<&'a [u8]> {
+    if count == 0 { return None; }
+    let stride = values.len() / count;
+    if stride == 0 { return None; }
+
+    let (mut low, mut high) = (0, count);
+    let mut found = None;
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match id_at(mid).cmp(&id) {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => {
+                found = Some(mid);
+                break;
+            }
+        }
+    }
+
+    let i = match found {
+        Some(i) => i,
+        None => (0..count).find(|&i| id_at(i) == id)?,
+    };
+    Some(&values[stride * i..stride * (i + 1)])
+}
+
 
 // Assignment messages
 impl Messages {
     pub fn iter_witness(&self) -> WitnessIterator {
         WitnessIterator {
             messages_iter: self.into_iter(),
-            var_ids: &[],
+            var_ids: Vector::default(),
             values: &[],
             next_element: 0,
         }
@@ -494,7 +705,7 @@ pub struct WitnessIterator<'a> {
     messages_iter: MessageIterator<'a>,
 
     // Iterate over variables in the current message.
-    var_ids: &'a [u64],
+    var_ids: Vector<'a, u64>,
     values: &'a [u8],
     next_element: usize,
 }
@@ -514,18 +725,18 @@ impl<'a> Iterator for WitnessIterator<'a> {
             };
 
             // Start iterating the values of the current message.
-            self.var_ids = witness.variable_ids().unwrap().safe_slice();
-            self.values = witness.values().unwrap();
+            self.var_ids = witness.variable_ids().unwrap();
+            self.values = witness.values().unwrap().bytes();
             self.next_element = 0;
         }
 
-        let stride = get_value_size(self.var_ids, self.values);
+        let stride = self.values.len() / self.var_ids.len();
 
         let i = self.next_element;
         self.next_element += 1;
 
         Some(Variable {
-            id: self.var_ids[i],
+            id: self.var_ids.get(i),
             value: &self.values[stride * i..stride * (i + 1)],
         })
     }
@@ -563,3 +774,109 @@ fn test_pretty_print_var() {
         value: &[9, 8, 0, 6, 0, 0],
     }), "var_4=[9,8,0,6]");
 }
+
+#[test]
+fn test_message_reader() {
+    use crate::examples::*;
+
+    let mut buf = Vec::<u8>::new();
+    example_circuit().write_into(&mut buf).unwrap();
+    example_constraints().write_into(&mut buf).unwrap();
+    example_witness().write_into(&mut buf).unwrap();
+
+    let mut reader = MessageReader::new(&buf[..]);
+    assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::Circuit(example_circuit())));
+    assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::ConstraintSystem(example_constraints())));
+    assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::Witness(example_witness())));
+    assert_eq!(reader.next_message().unwrap(), None);
+
+    // Truncated within the last message.
+    let mut reader = MessageReader::new(&buf[..buf.len() - 1]);
+    reader.next_message().unwrap();
+    reader.next_message().unwrap();
+    let err = reader.next_message().unwrap_err();
+    assert!(err.to_string().starts_with("truncated message"));
+
+    // Truncated within a size prefix.
+    let mut reader = MessageReader::new(&buf[..2]);
+    assert!(reader.next_message().unwrap_err().to_string().starts_with("truncated message"));
+}
+
+#[test]
+fn test_message_reader_progress() {
+    use crate::examples::*;
+
+    let mut buf = Vec::<u8>::new();
+    for _ in 0..3000 {
+        example_witness().write_into(&mut buf).unwrap();
+    }
+    let message_size = buf.len() as u64 / 3000;
+
+    let mut reports = vec![];
+    let mut reader = MessageReader::new(&buf[..]).with_progress(|progress| reports.push(progress));
+    while reader.next_message().unwrap().is_some() {}
+    assert_eq!(reader.next_message().unwrap(), None);
+    drop(reader);
+
+    // A report for every 64 KiB, and one at the end.
+    let interval_messages = PROGRESS_INTERVAL.div_ceil(message_size);
+    assert_eq!(reports.len() as u64, 3000 / interval_messages + 1);
+    assert_eq!(reports[0], Progress { bytes: interval_messages * message_size, messages: interval_messages, constraints: 0 });
+    assert_eq!(*reports.last().unwrap(), Progress { bytes: buf.len() as u64, messages: 3000, constraints: 0 });
+}
+
+#[test]
+fn test_max_message_size() {
+    use crate::examples::*;
+
+    // A prefix announcing almost 4 GiB, with no content: rejected before allocating or reading.
+    let mut reader = MessageReader::new(&[0xff, 0xff, 0xff, 0xff][..]);
+    let err = reader.next_message().unwrap_err();
+    assert!(matches!(err, ZkiError::MessageTooLarge { size: 0xffff_ffff, max: DEFAULT_MAX_MESSAGE_SIZE }));
+
+    let mut buf = Vec::<u8>::new();
+    example_circuit().write_into(&mut buf).unwrap();
+    let size = buf.len() - SIZE_UOFFSET;
+    let mut reader = MessageReader::with_max_message_size(&buf[..], size);
+    assert_eq!(reader.next_message().unwrap(), Some(MessageOwned::Circuit(example_circuit())));
+    let mut reader = MessageReader::with_max_message_size(&buf[..], size - 1);
+    assert_eq!(reader.next_message().unwrap_err().to_string(),
+               format!("a message of {} bytes exceeds the limit of {} bytes", size, size - 1));
+}
+
+#[test]
+fn test_witness_value() {
+    use crate::{VariablesOwned, WitnessOwned};
+
+    let n = 10_000u64;
+    let witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: (0..n).map(|i| 2 * i + 1).collect(),
+            values: Some((0..n as u32).flat_map(|i| (i * 3).to_le_bytes()).collect()),
+        },
+    };
+    let mut buf = Vec::<u8>::new();
+    witness.write_into(&mut buf).unwrap();
+    let witness_ref = parse_message(&buf).unwrap().message_as_witness().unwrap();
+
+    assert_eq!(witness_value(witness_ref, 1), Some(&0u32.to_le_bytes()[..]));
+    assert_eq!(witness_value(witness_ref, 2 * 4321 + 1), Some(&(4321u32 * 3).to_le_bytes()[..]));
+    assert_eq!(witness_value(witness_ref, 2 * (n - 1) + 1), Some(&((n as u32 - 1) * 3).to_le_bytes()[..]));
+    assert_eq!(witness_value(witness_ref, 4321 * 2), None);
+    assert_eq!(witness_value(witness_ref, 2 * n + 1), None);
+
+    // Unsorted IDs, where the binary search alone misses.
+    let witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![5, 9, 1, 3, 7],
+            values: Some(vec![50, 90, 10, 30, 70]),
+        },
+    };
+    let mut buf = Vec::<u8>::new();
+    witness.write_into(&mut buf).unwrap();
+    let witness_ref = parse_message(&buf).unwrap().message_as_witness().unwrap();
+    for &id in &[1, 3, 5, 7, 9] {
+        assert_eq!(witness_value(witness_ref, id), Some(&[id as u8 * 10][..]));
+    }
+    assert_eq!(witness_value(witness_ref, 4), None);
+}
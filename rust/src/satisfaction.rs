@@ -0,0 +1,305 @@
+//! Check that a witness satisfies a constraint system.
+
+use std::collections::HashMap;
+
+use crate::{Result, ZkiError, CircuitOwned, Endianness, ConstraintSystemOwned, VariablesOwned, WitnessOwned};
+use crate::field::{Field, Uint};
+use crate::reading::{Progress, PROGRESS_INTERVAL};
+
+
+/// Check that the values of the circuit connections and of the witness satisfy every constraint
+/// `(A·z) * (B·z) = (C·z)`, where `z` is the full assignment, modulo `field_maximum + 1`.
+///
+/// Values are read in the byte order of `CircuitOwned::value_endianness`.
+/// The variable 0 is the constant one. Assigning the same variable more than once, or
+/// referencing a variable without value, is an error. Returns an error naming the first
+/// failing constraint.
+pub fn verify_satisfaction(circuit: &CircuitOwned, cs: &ConstraintSystemOwned, witness: &WitnessOwned) -> Result<()> {
+    let mut verifier = SatisfactionVerifier::new(circuit, cs)?;
+    verifier.assign(&witness.assigned_variables, false)?;
+    verifier.finalize()
+}
+
+/// Like `verify_satisfaction`, reporting the number of constraints checked, see
+/// `SatisfactionVerifier::finalize_with_progress`.
+pub fn verify_satisfaction_with_progress(circuit: &CircuitOwned, cs: &ConstraintSystemOwned, witness: &WitnessOwned, progress: impl FnMut(Progress)) -> Result<()> {
+    let mut verifier = SatisfactionVerifier::new(circuit, cs)?;
+    verifier.assign(&witness.assigned_variables, false)?;
+    verifier.finalize_with_progress(progress)
+}
+
+/// Check satisfaction like `verify_satisfaction`, with the witness given in chunks.
+///
+/// A variable may be assigned in several chunks, but only with the same value.
+pub struct SatisfactionVerifier<'a> {
+    cs: &'a ConstraintSystemOwned,
+    field: Field,
+    endianness: Endianness,
+    assignment: HashMap<u64, Uint>,
+}
+
+impl<'a> SatisfactionVerifier<'a> {
+    /// Start with the values of the connections of the circuit.
+    pub fn new(circuit: &CircuitOwned, cs: &'a ConstraintSystemOwned) -> Result<SatisfactionVerifier<'a>> {
+        let field_maximum = circuit.field_maximum.as_ref()
+            .ok_or_else(|| ZkiError::MissingField { name: "field_maximum".to_string() })?;
+        let endianness = circuit.value_endianness()?;
+
+        let mut verifier = SatisfactionVerifier {
+            cs,
+            field: Field::from_maximum(&endianness.to_little_endian(field_maximum)),
+            endianness,
+            assignment: HashMap::new(),
+        };
+        verifier.assignment.insert(0, Uint::one());
+        verifier.assign(&circuit.connections, false)?;
+        Ok(verifier)
+    }
+
+    /// Add the values of a chunk of the witness.
+    pub fn feed(&mut self, witness: &WitnessOwned) -> Result<()> {
+        self.assign(&witness.assigned_variables, true)
+    }
+
+    /// Check all constraints, once all values are known.
+    pub fn finalize(self) -> Result<()> {
+        for i in 0..self.cs.constraints.len() {
+            self.check_constraint(i)?;
+        }
+        Ok(())
+    }
+
+    /// Like `finalize`, reporting the number of constraints checked to `progress` every
+    /// `PROGRESS_INTERVAL` constraints and at the end.
+    pub fn finalize_with_progress(self, mut progress: impl FnMut(Progress)) -> Result<()> {
+        let count = self.cs.constraints.len();
+        let mut unreported = 0;
+        for i in 0..count {
+            self.check_constraint(i)?;
+            unreported += 1;
+            if unreported == PROGRESS_INTERVAL || i + 1 == count {
+                unreported = 0;
+                progress(Progress { constraints: i as u64 + 1, ..Progress::default() });
+            }
+        }
+        Ok(())
+    }
+
+    /// Like `finalize`, with the constraints checked in parallel.
+    /// The error is the same, about the first failing constraint.
+    #[cfg(feature = "parallel")]
+    pub fn finalize_parallel(self) -> Result<()> {
+        use rayon::prelude::*;
+
+        let first_failure = (0..self.cs.constraints.len()).into_par_iter()
+            .filter_map(|i| self.check_constraint(i).err().map(|err| (i, err)))
+            .min_by_key(|(i, _)| *i);
+        match first_failure {
+            Some((_, err)) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    fn check_constraint(&self, i: usize) -> Result<()> {
+        let constraint = &self.cs.constraints[i];
+        let a = self.evaluate(&constraint.linear_combination_a)?;
+        let b = self.evaluate(&constraint.linear_combination_b)?;
+        let c = self.evaluate(&constraint.linear_combination_c)?;
+
+        if self.field.mul(&a, &b) != c {
+            return Err(format!("constraint {} is not satisfied", i).into());
+        }
+        Ok(())
+    }
+
+    fn assign(&mut self, vars: &VariablesOwned, allow_same_value: bool) -> Result<()> {
+        for var in vars.get_variables() {
+            if !var.has_value() { continue; }
+
+            let value = self.field.reduce(&read_value(var.value, self.endianness));
+            if let Some(previous) = self.assignment.insert(var.id, value.clone()) {
+                if !(allow_same_value && previous == value) {
+                    return Err(format!("variable {} is assigned more than once", var.id).into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&self, lc: &VariablesOwned) -> Result<Uint> {
+        let mut sum = Uint::zero();
+        for term in lc.get_variables() {
+            let value = self.assignment.get(&term.id)
+                .ok_or(ZkiError::DanglingVariable { id: term.id })?;
+            let coeff = read_value(term.value, self.endianness);
+            sum = self.field.add(&sum, &self.field.mul(&coeff, value));
+        }
+        Ok(sum)
+    }
+}
+
+fn read_value(value: &[u8], endianness: Endianness) -> Uint {
+    match endianness {
+        Endianness::Little => Uint::from_le_bytes(value),
+        Endianness::Big => Uint::from_le_bytes(&endianness.to_little_endian(value)),
+    }
+}
+
+
+#[test]
+fn test_verify_satisfaction() {
+    use crate::examples::*;
+    use crate::KeyValueOwned;
+
+    let circuit = example_circuit();
+    let cs = example_constraints();
+    verify_satisfaction(&circuit, &cs, &example_witness()).unwrap();
+
+    // Wrong witness: x^2 + y^2 != zz.
+    let err = verify_satisfaction(&circuit, &cs, &example_witness_inputs(3, 5)).unwrap_err();
+    assert_eq!(err.to_string(), "constraint 1 is not satisfied");
+
+    // The constraints hold modulo 101: 3^2 + 7^2 = 58 = 159 mod 101.
+    verify_satisfaction(&example_circuit_inputs(3, 7, 58), &cs, &example_witness_inputs(3, 7)).unwrap();
+
+    // Missing value.
+    let mut witness = example_witness();
+    witness.assigned_variables = VariablesOwned {
+        variable_ids: vec![4],
+        values: Some(serialize_small(&[9u32])),
+    };
+    let err = verify_satisfaction(&circuit, &cs, &witness).unwrap_err();
+    assert_eq!(err.to_string(), "variable 5 is referenced but has no value");
+
+    // Duplicate assignment.
+    let mut witness = example_witness();
+    witness.assigned_variables = VariablesOwned {
+        variable_ids: vec![3, 4, 5],
+        values: Some(serialize_small(&[25u32, 9, 16])),
+    };
+    let err = verify_satisfaction(&circuit, &cs, &witness).unwrap_err();
+    assert_eq!(err.to_string(), "variable 3 is assigned more than once");
+
+    // Big-endian values, declared in the configuration.
+    // Reversing each value converts both ways.
+    let to_big = |vars: &mut VariablesOwned| vars.to_little_endian(Endianness::Big);
+    let mut circuit = example_circuit();
+    to_big(&mut circuit.connections);
+    circuit.field_maximum.as_mut().unwrap().reverse();
+    circuit.configuration = Some(vec![KeyValueOwned {
+        key: "value_endianness".to_string(),
+        text: Some("big".to_string()),
+        data: None,
+        number: 0,
+    }]);
+    let mut cs = example_constraints();
+    for constraint in &mut cs.constraints {
+        to_big(&mut constraint.linear_combination_a);
+        to_big(&mut constraint.linear_combination_b);
+        to_big(&mut constraint.linear_combination_c);
+    }
+    let mut witness = example_witness();
+    to_big(&mut witness.assigned_variables);
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+    assert!(verify_satisfaction(&example_circuit(), &cs, &witness).is_err());
+}
+
+#[test]
+fn test_satisfaction_verifier() {
+    use crate::examples::*;
+
+    let circuit = example_circuit();
+    let cs = example_constraints();
+    let chunk = |id: u64, value: u32| WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![id],
+            values: Some(serialize_small(&[value])),
+        },
+    };
+
+    // One chunk.
+    let mut verifier = SatisfactionVerifier::new(&circuit, &cs).unwrap();
+    verifier.feed(&example_witness()).unwrap();
+    verifier.finalize().unwrap();
+
+    // Two chunks, and a repeated value.
+    let mut verifier = SatisfactionVerifier::new(&circuit, &cs).unwrap();
+    verifier.feed(&chunk(5, 16)).unwrap();
+    verifier.feed(&chunk(4, 9)).unwrap();
+    verifier.feed(&chunk(5, 16)).unwrap();
+    verifier.finalize().unwrap();
+
+    // Conflicting value.
+    let mut verifier = SatisfactionVerifier::new(&circuit, &cs).unwrap();
+    verifier.feed(&chunk(4, 9)).unwrap();
+    let err = verifier.feed(&chunk(4, 10)).unwrap_err();
+    assert_eq!(err.to_string(), "variable 4 is assigned more than once");
+
+    // Missing chunk.
+    let mut verifier = SatisfactionVerifier::new(&circuit, &cs).unwrap();
+    verifier.feed(&chunk(4, 9)).unwrap();
+    let err = verifier.finalize().unwrap_err();
+    assert!(matches!(err, ZkiError::DanglingVariable { id: 5 }));
+
+    // Wrong value.
+    let mut verifier = SatisfactionVerifier::new(&circuit, &cs).unwrap();
+    verifier.feed(&chunk(4, 9)).unwrap();
+    verifier.feed(&chunk(5, 17)).unwrap();
+    assert!(verifier.finalize().is_err());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_finalize_parallel() {
+    use crate::CircuitBuilder;
+
+    // x_i * x_i = y_i for 2000 pairs, with wrong squares at 700 and 1500.
+    let mut builder = CircuitBuilder::new(Some(vec![0xff, 0xff, 0xff, 0xff]));
+    for i in 0..2000u64 {
+        let x = builder.alloc_witness();
+        let y = builder.alloc_witness();
+        let square = if i == 700 || i == 1500 { i * i + 1 } else { i * i };
+        builder.assign(x, i.to_le_bytes().to_vec());
+        builder.assign(y, square.to_le_bytes().to_vec());
+        builder.constrain(vec![(x, vec![1])], vec![(x, vec![1])], vec![(y, vec![1])]);
+    }
+    let (circuit, cs, witness) = builder.finish();
+
+    let check = |witness: &WitnessOwned, parallel: bool| {
+        let mut verifier = SatisfactionVerifier::new(&circuit, &cs).unwrap();
+        verifier.feed(witness).unwrap();
+        let result = if parallel { verifier.finalize_parallel() } else { verifier.finalize() };
+        result.map_err(|err| err.to_string())
+    };
+    assert_eq!(check(&witness, true), Err("constraint 700 is not satisfied".to_string()));
+    assert_eq!(check(&witness, true), check(&witness, false));
+
+    let mut fixed = witness.clone();
+    let mut values = fixed.assigned_variables.values.take().unwrap();
+    values[8 * (2 * 700 + 1)] -= 1;
+    values[8 * (2 * 1500 + 1)] -= 1;
+    fixed.assigned_variables.values = Some(values);
+    assert_eq!(check(&fixed, true), Ok(()));
+    assert_eq!(check(&fixed, false), Ok(()));
+}
+
+#[test]
+fn test_finalize_with_progress() {
+    use crate::examples::*;
+    use crate::ConstraintOwned;
+
+    // 150000 constraints 1 * 1 = 1.
+    let one = vec![(0, vec![1])];
+    let cs: ConstraintSystemOwned = (0..150_000)
+        .map(|_| ConstraintOwned { a: one.clone(), b: one.clone(), c: one.clone() })
+        .collect();
+    let mut reports = vec![];
+    verify_satisfaction_with_progress(&example_circuit(), &cs, &example_witness(), |progress| reports.push(progress.constraints)).unwrap();
+    assert_eq!(reports, vec![65536, 131072, 150000]);
+
+    // No report after a failure.
+    let mut reports = vec![];
+    let err = verify_satisfaction_with_progress(&example_circuit(), &example_constraints(), &example_witness_inputs(3, 5), |progress| reports.push(progress.constraints)).unwrap_err();
+    assert_eq!(err.to_string(), "constraint 1 is not satisfied");
+    assert_eq!(reports, Vec::<u64>::new());
+}
@@ -0,0 +1,204 @@
+//! Complete a witness with the values determined by the constraints.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{Result, ZkiError, CircuitOwned, ConstraintSystemOwned, Endianness, VariablesOwned, WitnessOwned};
+use crate::field::{Field, Uint};
+use crate::owned::constraints::LinComb;
+
+
+/// Fill in the witness variables that are determined by a single constraint once the other
+/// values are known, as in feed-forward gadgets.
+///
+/// A constraint is solved when exactly one of its variables is unknown, and that variable does
+/// not appear in both A and B, so the constraint is linear in it. Solving a variable may make
+/// other constraints solvable, until no more constraint can be solved. The known values are the connections of the circuit and the
+/// values of `partial`.
+///
+/// The solved variables are appended to `partial`, sorted by ID. All values of the witness are
+/// then encoded with the width of the field, in the byte order of the circuit.
+/// If some variables remain unknown, `partial` is completed as far as possible and the error
+/// lists the remaining variables.
+pub fn complete_witness(circuit: &CircuitOwned, cs: &ConstraintSystemOwned, partial: &mut WitnessOwned) -> Result<()> {
+    let field_maximum = circuit.field_maximum.as_ref()
+        .ok_or_else(|| ZkiError::MissingField { name: "field_maximum".to_string() })?;
+    let endianness = circuit.value_endianness()?;
+    let field = Field::from_maximum(&endianness.to_little_endian(field_maximum));
+    let read = |value: &[u8]| field.reduce(&Uint::from_le_bytes(&endianness.to_little_endian(value)));
+
+    let mut known = HashMap::<u64, Uint>::new();
+    known.insert(0, Uint::one());
+    for var in circuit.connections.get_variables() {
+        if var.has_value() {
+            known.insert(var.id, read(var.value));
+        }
+    }
+    let mut witness: Vec<(u64, Uint)> = partial.assigned_variables.get_variables().iter()
+        .filter(|var| var.has_value())
+        .map(|var| (var.id, read(var.value)))
+        .collect();
+    known.extend(witness.iter().cloned());
+
+    type Terms = Vec<(u64, Uint)>;
+    let to_terms = |lc: &VariablesOwned| -> Terms {
+        lc.get_variables().iter().map(|term| (term.id, read(term.value))).collect()
+    };
+    let constraints: Vec<(Terms, Terms, Terms)> = cs.constraints.iter()
+        .map(|c| (
+            to_terms(&c.linear_combination_a),
+            to_terms(&c.linear_combination_b),
+            to_terms(&c.linear_combination_c),
+        ))
+        .collect();
+
+    // Index the constraints by variable, and drive the solving from a worklist of the
+    // constraints with exactly one unknown, so that each constraint is visited a few times.
+    let mut by_variable = HashMap::<u64, Vec<usize>>::new();
+    let mut unknown_counts = Vec::with_capacity(constraints.len());
+    for (i, (a, b, c)) in constraints.iter().enumerate() {
+        let unknown: BTreeSet<u64> = a.iter().chain(b).chain(c)
+            .map(|(id, _)| *id)
+            .filter(|id| !known.contains_key(id))
+            .collect();
+        for &id in &unknown {
+            by_variable.entry(id).or_default().push(i);
+        }
+        unknown_counts.push(unknown.len());
+    }
+    let mut worklist: Vec<usize> = (0..constraints.len())
+        .filter(|&i| unknown_counts[i] == 1)
+        .rev()
+        .collect();
+
+    let mut solved = vec![];
+    while let Some(i) = worklist.pop() {
+        if unknown_counts[i] != 1 { continue; }
+        let (a, b, c) = &constraints[i];
+        let id = match a.iter().chain(b).chain(c).find(|(id, _)| !known.contains_key(id)) {
+            Some((id, _)) => *id,
+            None => continue,
+        };
+
+        // Split each combination into (coefficient of the unknown, value of the rest).
+        let split = |lc: &Terms| {
+            let mut coeff = Uint::zero();
+            let mut rest = Uint::zero();
+            for (term_id, term_coeff) in lc {
+                match known.get(term_id) {
+                    Some(value) => rest = field.add(&rest, &field.mul(term_coeff, value)),
+                    None => coeff = field.add(&coeff, term_coeff),
+                }
+            }
+            (coeff, rest)
+        };
+        let (ca, ra) = split(a);
+        let (cb, rb) = split(b);
+        let (cc, rc) = split(c);
+        if !ca.is_zero() && !cb.is_zero() { continue; } // Quadratic in the unknown.
+
+        // (ca·u + ra) * (cb·u + rb) = cc·u + rc, that is u·(ca·rb + cb·ra - cc) = rc - ra·rb.
+        let factor = field.sub(&field.add(&field.mul(&ca, &rb), &field.mul(&cb, &ra)), &cc);
+        let inverse = match field.inv(&factor) {
+            Some(inverse) => inverse,
+            None => continue, // The constraint does not determine the unknown.
+        };
+        let value = field.mul(&field.sub(&rc, &field.mul(&ra, &rb)), &inverse);
+        known.insert(id, value.clone());
+        solved.push((id, value));
+
+        for &j in by_variable.get(&id).into_iter().flatten() {
+            unknown_counts[j] -= 1;
+            if unknown_counts[j] == 1 {
+                worklist.push(j);
+            }
+        }
+    }
+
+    solved.sort_by_key(|(id, _)| *id);
+    witness.extend(solved);
+    let terms: LinComb = witness.iter()
+        .map(|(id, value)| {
+            let mut bytes = value.to_le_bytes(field.byte_width());
+            if endianness == Endianness::Big {
+                bytes.reverse();
+            }
+            (*id, bytes)
+        })
+        .collect();
+    let declared = partial.assigned_variables.variable_ids.clone();
+    partial.assigned_variables = VariablesOwned::from(&terms);
+
+    let remaining: Vec<u64> = constraints.iter()
+        .flat_map(|(a, b, c)| a.iter().chain(b).chain(c).map(|(id, _)| *id))
+        .chain(declared)
+        .filter(|id| !known.contains_key(id))
+        .collect::<BTreeSet<u64>>()
+        .into_iter().collect();
+    if remaining.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("the values of variables {:?} cannot be determined", remaining).into())
+    }
+}
+
+
+#[test]
+fn test_complete_witness() {
+    use crate::{CircuitBuilder, ConstraintOwned};
+    use crate::owned::constraints::BilinearConstraintOwned;
+    use crate::satisfaction::verify_satisfaction;
+
+    // b = a * a, c = b * a, d = c * a, 1 * (2·e) = d, listed from the last step.
+    let mut builder = CircuitBuilder::new(Some(vec![100]));
+    let a = builder.alloc_witness();
+    let b = builder.alloc_witness();
+    let c = builder.alloc_witness();
+    let d = builder.alloc_witness();
+    let e = builder.alloc_witness();
+    builder.assign(a, vec![3]);
+    builder.constrain(vec![(0, vec![1])], vec![(e, vec![2])], vec![(d, vec![1])]);
+    builder.constrain(vec![(c, vec![1])], vec![(a, vec![1])], vec![(d, vec![1])]);
+    builder.constrain(vec![(b, vec![1])], vec![(a, vec![1])], vec![(c, vec![1])]);
+    builder.constrain(vec![(a, vec![1])], vec![(a, vec![1])], vec![(b, vec![1])]);
+    let (circuit, cs, _) = builder.finish();
+
+    let mut witness = WitnessOwned {
+        assigned_variables: VariablesOwned { variable_ids: vec![a], values: Some(vec![3]) },
+    };
+    complete_witness(&circuit, &cs, &mut witness).unwrap();
+    // e = 81 / 2 = 91 mod 101.
+    assert_eq!(witness.assigned_variables, VariablesOwned {
+        variable_ids: vec![1, 2, 3, 4, 5],
+        values: Some(vec![3, 9, 27, 81, 91]),
+    });
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+
+    // f * f = a cannot be solved; the rest is still completed.
+    let mut cs = cs;
+    cs.constraints.push(BilinearConstraintOwned::from(&ConstraintOwned {
+        a: vec![(6, vec![1])],
+        b: vec![(6, vec![1])],
+        c: vec![(a, vec![1])],
+    }));
+    let mut witness = WitnessOwned {
+        assigned_variables: VariablesOwned { variable_ids: vec![a], values: Some(vec![3]) },
+    };
+    let err = complete_witness(&circuit, &cs, &mut witness).unwrap_err();
+    assert_eq!(err.to_string(), "the values of variables [6] cannot be determined");
+    assert_eq!(witness.assigned_variables.variable_ids, vec![1, 2, 3, 4, 5]);
+
+    // A long chain x[i + 1] = x[i] * x[0], listed from the last step, is solved in one sweep.
+    let n = 20_000;
+    let mut builder = CircuitBuilder::new(Some(vec![100]));
+    let x: Vec<u64> = (0..=n).map(|_| builder.alloc_witness()).collect();
+    for i in (0..n).rev() {
+        builder.constrain(vec![(x[i], vec![1])], vec![(x[0], vec![1])], vec![(x[i + 1], vec![1])]);
+    }
+    let (circuit, cs, _) = builder.finish();
+    let mut witness = WitnessOwned {
+        assigned_variables: VariablesOwned { variable_ids: vec![x[0]], values: Some(vec![2]) },
+    };
+    complete_witness(&circuit, &cs, &mut witness).unwrap();
+    assert_eq!(witness.assigned_variables.variable_ids.len(), n + 1);
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+}
@@ -2,8 +2,44 @@ use std::io::Write;
 use std::fs::{File, create_dir_all};
 use std::path::{Path, PathBuf};
 
-use crate::Result;
-use crate::{CircuitOwned, ConstraintSystemOwned, WitnessOwned};
+use std::collections::BTreeSet;
+
+use crate::{Result, ZkiError};
+use crate::{CircuitOwned, ConstraintOwned, ConstraintSystemOwned, WitnessOwned};
+use crate::validation::validate_ids;
+
+
+/// A circuit with its constraints in several ConstraintSystem messages, as emitted separately
+/// by composed gadgets. All chunks share the variable IDs of the circuit.
+#[derive(Clone, Default, Debug, Eq, PartialEq)]
+pub struct Statement {
+    pub circuit: CircuitOwned,
+    pub constraint_systems: Vec<ConstraintSystemOwned>,
+}
+
+impl Statement {
+    /// The constraints of all chunks in order, with their IDs unchanged.
+    pub fn all_constraints(&self) -> impl Iterator<Item=ConstraintOwned> + '_ {
+        self.constraint_systems.iter().flat_map(|cs| cs.iter_constraints())
+    }
+
+    /// Check that the constraints of all chunks reference only defined variables, like
+    /// `validate_ids` with the constant one. The error lists the dangling IDs of all chunks.
+    pub fn validate(&self) -> Result<()> {
+        let mut dangling = BTreeSet::new();
+        for cs in &self.constraint_systems {
+            match validate_ids(&self.circuit, cs, true) {
+                Err(ZkiError::DanglingVariables { ids }) => dangling.extend(ids),
+                other => other?,
+            }
+        }
+        if dangling.is_empty() {
+            Ok(())
+        } else {
+            Err(ZkiError::DanglingVariables { ids: dangling.into_iter().collect() })
+        }
+    }
+}
 
 pub trait GadgetCallbacks {
     fn receive_constraints(&mut self, _msg: &[u8]) -> Result<()> { Ok(()) }
@@ -149,3 +185,30 @@ impl GadgetCallbacks for FileStore {
         Ok(())
     }
 }
+
+
+#[test]
+fn test_statement() {
+    use crate::examples::*;
+
+    let cs = example_constraints();
+    let mut statement = Statement {
+        circuit: example_circuit(),
+        constraint_systems: vec![
+            ConstraintSystemOwned { constraints: cs.constraints[..2].to_vec() },
+            ConstraintSystemOwned { constraints: cs.constraints[2..].to_vec() },
+        ],
+    };
+    statement.validate().unwrap();
+    let all: Vec<ConstraintOwned> = statement.all_constraints().collect();
+    assert_eq!(all, cs.iter_constraints().collect::<Vec<_>>());
+
+    statement.constraint_systems[0].constraints[1].linear_combination_a.variable_ids = vec![7];
+    statement.constraint_systems[1].constraints[0].linear_combination_c.variable_ids = vec![9];
+    let err = statement.validate().unwrap_err();
+    assert_eq!(err.to_string(), "the constraints reference undefined variables [7, 9]");
+
+    statement.circuit.free_variable_id = 8;
+    let err = statement.validate().unwrap_err();
+    assert_eq!(err.to_string(), "the constraints reference undefined variables [9]");
+}
@@ -2,9 +2,10 @@ extern crate serde;
 extern crate serde_json;
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 use crate::reading::Messages;
-use crate::Result;
+use crate::{Result, CircuitOwned, ConstraintSystemOwned};
 
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 pub struct Stats {
@@ -44,3 +45,59 @@ impl Stats {
         Ok(())
     }
 }
+
+
+/// Counts of a statement made of a circuit and its constraint system.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Statistics {
+    pub num_instance_variables: u64,
+    /// Distinct variables used in constraints, other than connections and the constant one.
+    pub num_local_variables: u64,
+    pub num_constraints: u64,
+    /// The highest ID among the connections and the variables used in constraints.
+    pub max_variable_id: u64,
+}
+
+pub fn statistics(circuit: &CircuitOwned, cs: &ConstraintSystemOwned) -> Statistics {
+    let connections: HashSet<u64> = circuit.connections.variable_ids.iter().cloned().collect();
+    let mut locals = HashSet::new();
+    let mut max_variable_id = connections.iter().cloned().max().unwrap_or(0);
+
+    for constraint in &cs.constraints {
+        for lc in &[
+            &constraint.linear_combination_a,
+            &constraint.linear_combination_b,
+            &constraint.linear_combination_c,
+        ] {
+            for &id in &lc.variable_ids {
+                max_variable_id = max_variable_id.max(id);
+                if id != 0 && !connections.contains(&id) {
+                    locals.insert(id);
+                }
+            }
+        }
+    }
+
+    Statistics {
+        num_instance_variables: connections.len() as u64,
+        num_local_variables: locals.len() as u64,
+        num_constraints: cs.constraints.len() as u64,
+        max_variable_id,
+    }
+}
+
+#[test]
+fn test_statistics() {
+    use crate::examples::*;
+
+    let stats = statistics(&example_circuit(), &example_constraints());
+    assert_eq!(stats, Statistics {
+        num_instance_variables: 3,
+        num_local_variables: 2,
+        num_constraints: 3,
+        max_variable_id: 5,
+    });
+
+    let json = serde_json::to_string(&stats).unwrap();
+    assert_eq!(json, r#"{"num_instance_variables":3,"num_local_variables":2,"num_constraints":3,"max_variable_id":5}"#);
+}
@@ -0,0 +1,89 @@
+//! Transformations of whole statements.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{CircuitOwned, ConstraintSystemOwned, VariablesOwned, WitnessOwned};
+
+
+/// Relabel the variables to the contiguous IDs `0..k`, consistently in the circuit, the
+/// constraints, and the witness. Returns the mapping from old to new IDs.
+///
+/// The used variables are the connections, the witness variables, and the variables of the
+/// constraints. They keep their relative order, and the constant one keeps the ID 0.
+/// The `free_variable_id` becomes `k`.
+pub fn compactify(circuit: &mut CircuitOwned, cs: &mut ConstraintSystemOwned, witness: &mut WitnessOwned) -> HashMap<u64, u64> {
+    let mut used = BTreeSet::new();
+    used.insert(0);
+    used.extend(circuit.connections.variable_ids.iter().cloned());
+    used.extend(witness.assigned_variables.variable_ids.iter().cloned());
+    for constraint in &cs.constraints {
+        for lc in &[
+            &constraint.linear_combination_a,
+            &constraint.linear_combination_b,
+            &constraint.linear_combination_c,
+        ] {
+            used.extend(lc.variable_ids.iter().cloned());
+        }
+    }
+
+    let mapping: HashMap<u64, u64> = used.iter().enumerate()
+        .map(|(new_id, &old_id)| (old_id, new_id as u64))
+        .collect();
+    let relabel = |vars: &mut VariablesOwned| {
+        for id in &mut vars.variable_ids {
+            *id = mapping[id];
+        }
+    };
+
+    relabel(&mut circuit.connections);
+    relabel(&mut witness.assigned_variables);
+    for constraint in &mut cs.constraints {
+        relabel(&mut constraint.linear_combination_a);
+        relabel(&mut constraint.linear_combination_b);
+        relabel(&mut constraint.linear_combination_c);
+    }
+    circuit.free_variable_id = used.len() as u64;
+
+    mapping
+}
+
+
+#[test]
+fn test_compactify() {
+    use crate::examples::*;
+    use crate::satisfaction::verify_satisfaction;
+
+    // Spread the IDs of the example: 1, 2, 3 -> 10, 20, 30, 4, 5 -> 400000, 500000.
+    let spread = |vars: &mut VariablesOwned| {
+        for id in &mut vars.variable_ids {
+            *id = match *id {
+                0 => 0,
+                1..=3 => *id * 10,
+                _ => *id * 100000,
+            };
+        }
+    };
+    let mut circuit = example_circuit();
+    let mut cs = example_constraints();
+    let mut witness = example_witness();
+    spread(&mut circuit.connections);
+    spread(&mut witness.assigned_variables);
+    for constraint in &mut cs.constraints {
+        spread(&mut constraint.linear_combination_a);
+        spread(&mut constraint.linear_combination_b);
+        spread(&mut constraint.linear_combination_c);
+    }
+    circuit.free_variable_id = 500001;
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+
+    let mapping = compactify(&mut circuit, &mut cs, &mut witness);
+    assert_eq!(mapping[&0], 0);
+    assert_eq!(mapping[&10], 1);
+    assert_eq!(mapping[&500000], 5);
+    assert_eq!(mapping.len(), 6);
+
+    assert_eq!(circuit, example_circuit());
+    assert_eq!(cs, example_constraints());
+    assert_eq!(witness, example_witness());
+    verify_satisfaction(&circuit, &cs, &witness).unwrap();
+}
@@ -0,0 +1,189 @@
+//! Consistency checks of statements.
+
+use std::collections::BTreeSet;
+
+use crate::{Result, ZkiError, CircuitOwned, ConstraintSystemOwned, WitnessOwned};
+use crate::field::Uint;
+
+
+/// Check that every variable referenced by the constraints is defined.
+///
+/// The defined variables are the instance variables, which are the connections of the circuit,
+/// and the witness variables, which are allocated from 1 up to its `free_variable_id`. This is
+/// the check of `check_free_boundary`, reported as a list of IDs. If `constant_one` is true, the
+/// ID 0 denotes the constant one and may be used; otherwise it is reported like any undefined
+/// variable.
+/// The error lists all dangling IDs.
+pub fn validate_ids(circuit: &CircuitOwned, cs: &ConstraintSystemOwned, constant_one: bool) -> Result<()> {
+    let mut dangling = BTreeSet::new();
+    if !constant_one && references(cs).any(|(_, id)| id == 0) {
+        dangling.insert(0);
+    }
+    match check_free_boundary(circuit, cs) {
+        Ok(()) => {}
+        Err(ZkiError::BoundaryViolation { references, .. }) => {
+            dangling.extend(references.into_iter()
+                .map(|(_, id)| id)
+                .filter(|&id| id != 0 || !constant_one));
+        }
+        Err(err) => return Err(err),
+    }
+
+    if dangling.is_empty() {
+        Ok(())
+    } else {
+        Err(ZkiError::DanglingVariables { ids: dangling.into_iter().collect() })
+    }
+}
+
+
+/// Check that the constraints reference no variable at or above the `free_variable_id` of the
+/// circuit, which would be an unallocated variable.
+///
+/// The ConstraintSystem message has no table of its own variables, so the only exception are
+/// connections of the circuit, which may be declared anywhere.
+/// The error lists all violations as (constraint index, variable ID).
+pub fn check_free_boundary(circuit: &CircuitOwned, cs: &ConstraintSystemOwned) -> Result<()> {
+    let references: BTreeSet<(usize, u64)> = references(cs)
+        .filter(|&(_, id)| id >= circuit.free_variable_id() && !circuit.is_instance(id))
+        .collect();
+
+    if references.is_empty() {
+        Ok(())
+    } else {
+        Err(ZkiError::BoundaryViolation {
+            free_variable_id: circuit.free_variable_id(),
+            references: references.into_iter().collect(),
+        })
+    }
+}
+
+/// All variable references of the constraints, as (constraint index, variable ID).
+fn references(cs: &ConstraintSystemOwned) -> impl Iterator<Item = (usize, u64)> + '_ {
+    cs.constraints.iter().enumerate().flat_map(|(i, constraint)| {
+        constraint.linear_combination_a.variable_ids.iter()
+            .chain(&constraint.linear_combination_b.variable_ids)
+            .chain(&constraint.linear_combination_c.variable_ids)
+            .map(move |&id| (i, id))
+    })
+}
+
+/// The variables assigned in the witness but referenced by no constraint, sorted.
+/// These are often dead wires, or a sign that the witness belongs to another circuit.
+///
+/// This is the converse of `validate_ids`. The constant one (ID 0) is never reported.
+pub fn unconstrained_variables(cs: &ConstraintSystemOwned, witness: &WitnessOwned) -> Vec<u64> {
+    let mut unconstrained: BTreeSet<u64> = witness.assigned_variables.variable_ids.iter()
+        .cloned()
+        .filter(|&id| id != 0)
+        .collect();
+    for (_, id) in references(cs) {
+        unconstrained.remove(&id);
+    }
+    unconstrained.into_iter().collect()
+}
+
+/// Check that every value of the witness is at most the `field_maximum` of the circuit, so it
+/// is an element of the field. Values may be longer than the width of the field.
+/// Values are read in the byte order of `CircuitOwned::value_endianness`.
+/// The error is a `FieldOverflow` with the first offending variable and its value.
+pub fn check_values_in_field(circuit: &CircuitOwned, witness: &WitnessOwned) -> Result<()> {
+    let endianness = circuit.value_endianness()?;
+    let field_maximum = circuit.field_maximum.as_ref()
+        .ok_or_else(|| ZkiError::MissingField { name: "field_maximum".to_string() })?;
+    let maximum = Uint::from_le_bytes(&endianness.to_little_endian(field_maximum));
+
+    for var in witness.assigned_variables.get_variables() {
+        let value = Uint::from_le_bytes(&endianness.to_little_endian(var.value));
+        if value > maximum {
+            return Err(ZkiError::FieldOverflow { id: Some(var.id), value: Some(var.value.to_vec()) });
+        }
+    }
+    Ok(())
+}
+
+
+#[test]
+fn test_validate_ids() {
+    use crate::examples::*;
+
+    let circuit = example_circuit();
+    let mut cs = example_constraints();
+    validate_ids(&circuit, &cs, true).unwrap();
+
+    let err = validate_ids(&circuit, &cs, false).unwrap_err();
+    assert_eq!(err.to_string(), "the constraints reference undefined variables [0]");
+
+    cs.constraints[0].linear_combination_c.variable_ids = vec![9];
+    cs.constraints[1].linear_combination_a.variable_ids = vec![6];
+    let err = validate_ids(&circuit, &cs, true).unwrap_err();
+    assert_eq!(err.to_string(), "the constraints reference undefined variables [6, 9]");
+
+    // A connection above the boundary is defined.
+    let mut circuit = circuit;
+    circuit.connections.variable_ids = vec![1, 2, 9];
+    let err = validate_ids(&circuit, &cs, false).unwrap_err();
+    assert_eq!(err.to_string(), "the constraints reference undefined variables [0, 6]");
+}
+
+#[test]
+fn test_check_free_boundary() {
+    use crate::examples::*;
+
+    let mut circuit = example_circuit();
+    let mut cs = example_constraints();
+    check_free_boundary(&circuit, &cs).unwrap();
+
+    cs.constraints[0].linear_combination_c.variable_ids = vec![6];
+    cs.constraints[2].linear_combination_b.variable_ids = vec![9, 5];
+    let err = check_free_boundary(&circuit, &cs).unwrap_err();
+    assert_eq!(err.to_string(),
+               "the constraints reference variables at or above free_variable_id 6, as (constraint, variable): [(0, 6), (2, 9)]");
+
+    // Connections may be above the boundary.
+    circuit.connections.variable_ids = vec![1, 6, 9];
+    check_free_boundary(&circuit, &cs).unwrap();
+}
+
+#[test]
+fn test_unconstrained_variables() {
+    use crate::examples::*;
+    use crate::VariablesOwned;
+
+    let cs = example_constraints();
+    assert_eq!(unconstrained_variables(&cs, &example_witness()), Vec::<u64>::new());
+
+    // The variable 7 is dead; the constant one is never reported.
+    let witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![7, 4, 0, 5],
+            values: Some(serialize_small(&[1u32, 9, 1, 16])),
+        },
+    };
+    assert_eq!(unconstrained_variables(&cs, &witness), vec![7]);
+}
+
+#[test]
+fn test_check_values_in_field() {
+    use crate::examples::*;
+    use crate::VariablesOwned;
+
+    let circuit = example_circuit();
+    check_values_in_field(&circuit, &example_witness()).unwrap();
+
+    // Long values, in range.
+    let mut witness = example_witness();
+    witness.assigned_variables.values = Some(vec![100, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    check_values_in_field(&circuit, &witness).unwrap();
+
+    // A value equal to the modulus 101.
+    let witness = WitnessOwned {
+        assigned_variables: VariablesOwned {
+            variable_ids: vec![4, 5],
+            values: Some(serialize_small(&[9u32, 101])),
+        },
+    };
+    let err = check_values_in_field(&circuit, &witness).unwrap_err();
+    assert!(matches!(err, ZkiError::FieldOverflow { id: Some(5), value: Some(ref value) } if value == &[101, 0, 0, 0]));
+    assert_eq!(err.to_string(), "the value of variable 5, with bytes 65000000, is too large for the field");
+}
@@ -0,0 +1,388 @@
+//! Structural verification of untrusted Flatbuffers messages.
+//!
+//! The generated accessors trust their buffer and may panic or read garbage
+//! if it is malformed. Verify a buffer here before reading it.
+
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::str;
+use flatbuffers::{SIZE_UOFFSET, VOffsetT};
+
+use crate::zkinterface_generated::zkinterface::{
+    BilinearConstraint,
+    Circuit,
+    Command,
+    ConstraintSystem,
+    KeyValue,
+    Message,
+    Root,
+    size_prefixed_root_as_root_unchecked,
+    Variables,
+    Witness,
+};
+use crate::{Result, ZkiError};
+
+
+/// Verify a message with its 4 bytes size prefix, then access it.
+/// This is the safe way to read an untrusted buffer; a malformed one gives an error.
+pub fn parse_message(buf: &[u8]) -> Result<Root<'_>> {
+    verify_size_prefixed_root(buf)?;
+    // Safe because the buffer was verified just above.
+    Ok(unsafe { size_prefixed_root_as_root_unchecked(buf) })
+}
+
+/// The type of a message, see `peek_message_type`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MessageKind {
+    Circuit,
+    ConstraintSystem,
+    Witness,
+    Command,
+}
+
+/// The type of a message with its size prefix, without verifying or parsing its content.
+/// Only the root table is verified. A message without type is an error.
+pub fn peek_message_type(buf: &[u8]) -> Result<MessageKind> {
+    if buf.len() < SIZE_UOFFSET {
+        return Err(ZkiError::FlatbufferVerification(format!("truncated message: {} bytes is too short for a size prefix", buf.len())));
+    }
+    let size = read_u32(buf, 0) as usize;
+    if size > buf.len() - SIZE_UOFFSET {
+        return Err(ZkiError::FlatbufferVerification(format!("truncated message: expected {} bytes, got {}", size, buf.len() - SIZE_UOFFSET)));
+    }
+    let v = Verifier { buf: &buf[SIZE_UOFFSET..SIZE_UOFFSET + size] };
+    let root = v.table(v.uoffset(0)?)?;
+
+    let message_type = match root.field(Root::VT_MESSAGE_TYPE, 1)? {
+        Some(pos) => v.buf[pos],
+        None => Message::NONE.0,
+    };
+    match message_type {
+        t if t == Message::Circuit.0 => Ok(MessageKind::Circuit),
+        t if t == Message::ConstraintSystem.0 => Ok(MessageKind::ConstraintSystem),
+        t if t == Message::Witness.0 => Ok(MessageKind::Witness),
+        t if t == Message::Command.0 => Ok(MessageKind::Command),
+        t if t == Message::NONE.0 => Err(ZkiError::FlatbufferVerification("invalid message: the message has no type".to_string())),
+        t => Err(ZkiError::FlatbufferVerification(format!("invalid message: unknown message type {}", t))),
+    }
+}
+
+/// Verify a message with its 4 bytes size prefix, as written by `write_into`.
+/// Bytes after the end of the message are ignored.
+pub fn verify_size_prefixed_root(buf: &[u8]) -> Result<()> {
+    if buf.len() < SIZE_UOFFSET {
+        return Err(ZkiError::FlatbufferVerification(format!("truncated message: {} bytes is too short for a size prefix", buf.len())));
+    }
+    let size = read_u32(buf, 0) as usize;
+    let available = buf.len() - SIZE_UOFFSET;
+    if size > available {
+        return Err(ZkiError::FlatbufferVerification(format!("truncated message: expected {} bytes, got {}", size, available)));
+    }
+    verify_root(&buf[SIZE_UOFFSET..SIZE_UOFFSET + size])
+}
+
+/// Verify a message without size prefix.
+pub fn verify_root(buf: &[u8]) -> Result<()> {
+    let v = Verifier { buf };
+    let root = v.table(v.uoffset(0)?)?;
+
+    let message_type = match root.field(Root::VT_MESSAGE_TYPE, 1)? {
+        Some(pos) => buf[pos],
+        None => Message::NONE.0,
+    };
+    let message = match root.table_field(Root::VT_MESSAGE)? {
+        Some(message) => message,
+        None => return Ok(()),
+    };
+
+    match message_type {
+        t if t == Message::Circuit.0 => v.circuit(message),
+        t if t == Message::ConstraintSystem.0 => v.constraint_system(message),
+        t if t == Message::Witness.0 => v.witness(message),
+        t if t == Message::Command.0 => v.command(message),
+        t if t == Message::NONE.0 => Ok(()),
+        t => Err(ZkiError::FlatbufferVerification(format!("invalid message: unknown message type {}", t))),
+    }
+}
+
+fn read_u16(buf: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([buf[pos], buf[pos + 1]])
+}
+
+fn read_u32(buf: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]])
+}
+
+struct Verifier<'a> {
+    buf: &'a [u8],
+}
+
+#[derive(Clone, Copy)]
+struct Table<'v, 'a> {
+    v: &'v Verifier<'a>,
+    pos: usize,
+    vtable: usize,
+    vtable_len: usize,
+    object_len: usize,
+}
+
+impl<'a> Verifier<'a> {
+    fn check_range(&self, pos: usize, len: usize) -> Result<()> {
+        match pos.checked_add(len) {
+            Some(end) if end <= self.buf.len() => Ok(()),
+            _ => Err(ZkiError::FlatbufferVerification(format!("invalid message: {} bytes at offset {} are out of bounds", len, pos))),
+        }
+    }
+
+    /// The generated accessors read scalars in place, which requires them to be aligned in memory.
+    fn check_aligned(&self, pos: usize, align: usize) -> Result<()> {
+        if (self.buf.as_ptr() as usize + pos) & (align - 1) != 0 {
+            return Err(ZkiError::FlatbufferVerification(format!("invalid message: offset {} is not aligned to {} bytes", pos, align)));
+        }
+        Ok(())
+    }
+
+    /// Follow the unsigned offset stored at `pos`.
+    fn uoffset(&self, pos: usize) -> Result<usize> {
+        self.check_range(pos, SIZE_UOFFSET)?;
+        self.check_aligned(pos, SIZE_UOFFSET)?;
+        let target = pos + read_u32(self.buf, pos) as usize;
+        self.check_range(target, 0)?;
+        Ok(target)
+    }
+
+    fn table<'v>(&'v self, pos: usize) -> Result<Table<'v, 'a>> {
+        self.check_range(pos, 4)?;
+        self.check_aligned(pos, 4)?;
+        let soffset = read_u32(self.buf, pos) as i32 as i64;
+        let vtable = pos as i64 - soffset;
+        if vtable < 0 {
+            return Err(ZkiError::FlatbufferVerification(format!("invalid message: vtable of table at offset {} is out of bounds", pos)));
+        }
+        let vtable = vtable as usize;
+        self.check_range(vtable, 4)?;
+        self.check_aligned(vtable, 2)?;
+        let vtable_len = read_u16(self.buf, vtable) as usize;
+        let object_len = read_u16(self.buf, vtable + 2) as usize;
+        if vtable_len < 4 || vtable_len & 1 != 0 {
+            return Err(ZkiError::FlatbufferVerification(format!("invalid message: vtable at offset {} has invalid size {}", vtable, vtable_len)));
+        }
+        self.check_range(vtable, vtable_len)?;
+        self.check_range(pos, object_len)?;
+        Ok(Table { v: self, pos, vtable, vtable_len, object_len })
+    }
+
+    /// Verify a vector and return the position and count of its elements.
+    fn vector(&self, pos: usize, elem_size: usize) -> Result<(usize, usize)> {
+        self.check_range(pos, SIZE_UOFFSET)?;
+        self.check_aligned(pos, SIZE_UOFFSET)?;
+        let count = read_u32(self.buf, pos) as usize;
+        let data = pos + SIZE_UOFFSET;
+        self.check_aligned(data, elem_size)?;
+        let len = count.checked_mul(elem_size)
+            .ok_or_else(|| format!("invalid message: vector at offset {} is too long", pos))?;
+        self.check_range(data, len)?;
+        Ok((data, count))
+    }
+
+    fn string(&self, pos: usize) -> Result<()> {
+        let (data, len) = self.vector(pos, 1)?;
+        // Strings are followed by a null terminator.
+        self.check_range(data, len + 1)?;
+        str::from_utf8(&self.buf[data..data + len])
+            .map_err(|_| format!("invalid message: string at offset {} is not valid UTF-8", pos))?;
+        Ok(())
+    }
+
+    fn table_vector<'v>(&'v self, pos: usize) -> Result<Vec<Table<'v, 'a>>> {
+        let (data, count) = self.vector(pos, SIZE_UOFFSET)?;
+        (0..count)
+            .map(|i| self.table(self.uoffset(data + SIZE_UOFFSET * i)?))
+            .collect()
+    }
+
+    fn circuit(&self, table: Table) -> Result<()> {
+        if let Some(connections) = table.table_field(Circuit::VT_CONNECTIONS)? {
+            self.variables(connections)?;
+        }
+        table.field(Circuit::VT_FREE_VARIABLE_ID, 8)?;
+        table.vector_field(Circuit::VT_FIELD_MAXIMUM, 1)?;
+        self.key_values(table, Circuit::VT_CONFIGURATION)
+    }
+
+    fn constraint_system(&self, table: Table) -> Result<()> {
+        if let Some(pos) = table.offset_field(ConstraintSystem::VT_CONSTRAINTS)? {
+            for constraint in self.table_vector(pos)? {
+                for &vt in &[
+                    BilinearConstraint::VT_LINEAR_COMBINATION_A,
+                    BilinearConstraint::VT_LINEAR_COMBINATION_B,
+                    BilinearConstraint::VT_LINEAR_COMBINATION_C,
+                ] {
+                    if let Some(lc) = constraint.table_field(vt)? {
+                        self.variables(lc)?;
+                    }
+                }
+            }
+        }
+        self.key_values(table, ConstraintSystem::VT_INFO)
+    }
+
+    fn witness(&self, table: Table) -> Result<()> {
+        if let Some(assigned) = table.table_field(Witness::VT_ASSIGNED_VARIABLES)? {
+            self.variables(assigned)?;
+        }
+        Ok(())
+    }
+
+    fn command(&self, table: Table) -> Result<()> {
+        table.field(Command::VT_CONSTRAINTS_GENERATION, 1)?;
+        table.field(Command::VT_WITNESS_GENERATION, 1)?;
+        self.key_values(table, Command::VT_PARAMETERS)
+    }
+
+    fn variables(&self, table: Table) -> Result<()> {
+        table.vector_field(Variables::VT_VARIABLE_IDS, 8)?;
+        table.vector_field(Variables::VT_VALUES, 1)?;
+        self.key_values(table, Variables::VT_INFO)
+    }
+
+    fn key_values(&self, table: Table, vt: VOffsetT) -> Result<()> {
+        if let Some(pos) = table.offset_field(vt)? {
+            for kv in self.table_vector(pos)? {
+                if let Some(key) = kv.offset_field(KeyValue::VT_KEY)? {
+                    self.string(key)?;
+                }
+                if let Some(text) = kv.offset_field(KeyValue::VT_TEXT)? {
+                    self.string(text)?;
+                }
+                kv.vector_field(KeyValue::VT_DATA, 1)?;
+                kv.field(KeyValue::VT_NUMBER, 8)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'v, 'a> Table<'v, 'a> {
+    /// Return the position of a present field of `size` bytes.
+    fn field(&self, vt: VOffsetT, size: usize) -> Result<Option<usize>> {
+        let vt = vt as usize;
+        if vt + 2 > self.vtable_len { return Ok(None); }
+        let offset = read_u16(self.v.buf, self.vtable + vt) as usize;
+        if offset == 0 { return Ok(None); }
+        if offset + size > self.object_len {
+            return Err(ZkiError::FlatbufferVerification(format!("invalid message: field of table at offset {} is out of bounds", self.pos)));
+        }
+        self.v.check_aligned(self.pos + offset, size)?;
+        Ok(Some(self.pos + offset))
+    }
+
+    /// Return the position of the object referenced by an offset field.
+    fn offset_field(&self, vt: VOffsetT) -> Result<Option<usize>> {
+        match self.field(vt, SIZE_UOFFSET)? {
+            Some(pos) => Ok(Some(self.v.uoffset(pos)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn table_field(&self, vt: VOffsetT) -> Result<Option<Table<'v, 'a>>> {
+        match self.offset_field(vt)? {
+            Some(pos) => Ok(Some(self.v.table(pos)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn vector_field(&self, vt: VOffsetT, elem_size: usize) -> Result<()> {
+        if let Some(pos) = self.offset_field(vt)? {
+            self.v.vector(pos, elem_size)?;
+        }
+        Ok(())
+    }
+}
+
+
+#[test]
+fn test_verify() {
+    use crate::examples::*;
+
+    let mut buf = vec![];
+    example_circuit().write_into(&mut buf).unwrap();
+    verify_size_prefixed_root(&buf).unwrap();
+
+    for len in 0..buf.len() {
+        assert!(verify_size_prefixed_root(&buf[..len]).is_err());
+    }
+
+    // Point the root offset out of the message.
+    let mut corrupt = buf.clone();
+    corrupt[SIZE_UOFFSET..2 * SIZE_UOFFSET].copy_from_slice(&1000u32.to_le_bytes());
+    assert!(verify_size_prefixed_root(&corrupt).is_err());
+
+    let mut buf = vec![];
+    write_example_constraints(&mut buf).unwrap();
+    write_example_witness(&mut buf).unwrap();
+    verify_size_prefixed_root(&buf).unwrap();
+    crate::CommandOwned::default().write_into(&mut buf).unwrap();
+    for msg in crate::reading::split_messages(&buf) {
+        verify_size_prefixed_root(msg).unwrap();
+    }
+}
+
+#[test]
+fn test_parse_message() {
+    use rand_core::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+    use crate::examples::*;
+    use crate::owned::message::MessageOwned;
+
+    let mut buf = vec![];
+    write_example_witness(&mut buf).unwrap();
+    let root = parse_message(&buf).unwrap();
+    assert_eq!(MessageOwned::from_root(root).unwrap(), MessageOwned::Witness(example_witness()));
+
+    // A valid message that is not aligned in memory.
+    let mut shifted = vec![0u8];
+    shifted.extend_from_slice(&buf);
+    assert!(parse_message(&shifted[1..]).unwrap_err().to_string().contains("is not aligned"));
+
+    // Random truncations and corruptions give errors or valid messages, never panics.
+    let rng = &mut XorShiftRng::from_seed([3; 16]);
+    for _ in 0..1000 {
+        let len = rng.next_u32() as usize % buf.len();
+        assert!(parse_message(&buf[..len]).is_err());
+
+        let mut corrupt = buf.clone();
+        let pos = rng.next_u32() as usize % buf.len();
+        corrupt[pos] = rng.next_u32() as u8;
+        if let Ok(root) = parse_message(&corrupt) {
+            let _ = MessageOwned::from_root(root);
+        }
+    }
+}
+
+#[test]
+fn test_peek_message_type() {
+    use crate::examples::*;
+    use crate::CommandOwned;
+
+    let kind = |write: &dyn Fn(&mut Vec<u8>)| {
+        let mut buf = vec![];
+        write(&mut buf);
+        peek_message_type(&buf)
+    };
+    assert_eq!(kind(&|buf| example_circuit().write_into(buf).unwrap()).unwrap(), MessageKind::Circuit);
+    assert_eq!(kind(&|buf| example_constraints().write_into(buf).unwrap()).unwrap(), MessageKind::ConstraintSystem);
+    assert_eq!(kind(&|buf| example_witness().write_into(buf).unwrap()).unwrap(), MessageKind::Witness);
+    assert_eq!(kind(&|buf| CommandOwned::default().write_into(buf).unwrap()).unwrap(), MessageKind::Command);
+
+    // Malformed: truncated prefix, truncated message, root offset out of the message.
+    let mut buf = vec![];
+    example_witness().write_into(&mut buf).unwrap();
+    assert!(peek_message_type(&buf[..2]).is_err());
+    assert!(peek_message_type(&buf[..buf.len() - 1]).is_err());
+    let mut corrupt = buf.clone();
+    corrupt[SIZE_UOFFSET..2 * SIZE_UOFFSET].copy_from_slice(&1000u32.to_le_bytes());
+    assert!(peek_message_type(&corrupt).is_err());
+    assert!(peek_message_type(&[4, 0, 0, 0, 0, 0, 0, 0]).is_err());
+}
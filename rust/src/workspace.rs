@@ -0,0 +1,94 @@
+//! Load all messages of a workspace directory.
+
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use crate::{Result, CircuitOwned, ConstraintSystemOwned, MessageOwned, MessageReader, MessagesOwned, WitnessOwned};
+
+
+/// The messages found in the `.zkif` files of a directory.
+#[derive(Clone, Debug, Default)]
+pub struct Workspace {
+    pub messages: MessagesOwned,
+    /// Files that could not be parsed, with the error.
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+impl Workspace {
+    /// Read all `.zkif` files of a directory, in the order of their file names.
+    /// Other files are skipped. Files that fail to parse are recorded in `errors`.
+    pub fn from_dir(path: &Path) -> Result<Workspace> {
+        let mut paths = vec![];
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension() == Some("zkif".as_ref()) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+
+        let mut workspace = Workspace::default();
+        for path in paths {
+            if let Err(err) = workspace.read_file(&path) {
+                workspace.errors.push((path, err.to_string()));
+            }
+        }
+        Ok(workspace)
+    }
+
+    /// Read the messages of a file. In case of error, the messages before the error are kept.
+    pub fn read_file(&mut self, path: &Path) -> Result<()> {
+        let mut reader = MessageReader::new(BufReader::new(File::open(path)?));
+        while let Some(message) = reader.next_message()? {
+            match message {
+                MessageOwned::Circuit(circuit) => self.messages.circuits.push(circuit),
+                MessageOwned::ConstraintSystem(cs) => self.messages.constraint_systems.push(cs),
+                MessageOwned::Witness(witness) => self.messages.witnesses.push(witness),
+                MessageOwned::Command(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn headers(&self) -> &[CircuitOwned] {
+        &self.messages.circuits
+    }
+
+    pub fn constraint_systems(&self) -> &[ConstraintSystemOwned] {
+        &self.messages.constraint_systems
+    }
+
+    pub fn witnesses(&self) -> &[WitnessOwned] {
+        &self.messages.witnesses
+    }
+}
+
+
+#[test]
+fn test_workspace() {
+    use std::io::Write;
+    use crate::examples::*;
+
+    let dir = std::env::temp_dir().join(format!("zkinterface_test_workspace_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    example_witness_inputs(5, 6).write_into(&mut File::create(dir.join("witness_2.zkif")).unwrap()).unwrap();
+    example_witness().write_into(&mut File::create(dir.join("witness_1.zkif")).unwrap()).unwrap();
+    let mut main = File::create(dir.join("main.zkif")).unwrap();
+    example_circuit().write_into(&mut main).unwrap();
+    write_example_constraints(&mut main).unwrap();
+    File::create(dir.join("notes.txt")).unwrap().write_all(b"not a message").unwrap();
+    File::create(dir.join("broken.zkif")).unwrap().write_all(&[100, 0, 0, 0, 1, 2]).unwrap();
+
+    let workspace = Workspace::from_dir(&dir).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(workspace.headers(), &[example_circuit()]);
+    assert_eq!(workspace.constraint_systems(), &[example_constraints()]);
+    assert_eq!(workspace.witnesses(), &[example_witness(), example_witness_inputs(5, 6)]);
+    assert_eq!(workspace.errors.len(), 1);
+    assert_eq!(workspace.errors[0].0, dir.join("broken.zkif"));
+    assert!(workspace.errors[0].1.starts_with("truncated message"));
+}
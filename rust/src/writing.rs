@@ -0,0 +1,168 @@
+//! Write messages incrementally.
+
+use std::io::Write;
+
+use crate::{Result, VariablesOwned, WitnessOwned};
+use crate::owned::constraints::LinComb;
+
+
+/// Write a witness value by value.
+///
+/// The values are buffered and written as one Witness message by `finish`. With chunking, a
+/// message is written every time a chunk is full, and the rest by `finish`. Chunks are
+/// bounded either by a number of values, or by the size of the encoded IDs and values.
+/// Variables are written in the order they are pushed; values are padded to the same length
+/// within each message.
+pub struct WitnessWriter<W: Write> {
+    writer: W,
+    chunking: Chunking,
+    buffer: LinComb,
+    value_len: usize,
+    written: bool,
+}
+
+enum Chunking {
+    None,
+    Values(usize),
+    Bytes(usize),
+}
+
+impl<W: Write> WitnessWriter<W> {
+    pub fn new(writer: W) -> WitnessWriter<W> {
+        WitnessWriter { writer, chunking: Chunking::None, buffer: vec![], value_len: 0, written: false }
+    }
+
+    /// Write a message for every `values_per_chunk` values.
+    pub fn with_values_per_chunk(writer: W, values_per_chunk: usize) -> WitnessWriter<W> {
+        assert!(values_per_chunk > 0, "the number of values per chunk must not be zero");
+        WitnessWriter { chunking: Chunking::Values(values_per_chunk), ..WitnessWriter::new(writer) }
+    }
+
+    /// Write a message before the encoded IDs and values of a chunk exceed `max_chunk_bytes`.
+    /// That is 8 bytes per ID, and the length of the longest value per value. The framing of
+    /// the message adds a few dozen bytes. A single variable larger than the limit is written
+    /// alone.
+    pub fn with_max_chunk_bytes(writer: W, max_chunk_bytes: usize) -> WitnessWriter<W> {
+        WitnessWriter { chunking: Chunking::Bytes(max_chunk_bytes), ..WitnessWriter::new(writer) }
+    }
+
+    pub fn push(&mut self, id: u64, value: Vec<u8>) -> Result<()> {
+        if let Chunking::Bytes(max_chunk_bytes) = self.chunking {
+            let value_len = self.value_len.max(value.len());
+            let chunk_bytes = (self.buffer.len() + 1) * (8 + value_len);
+            if !self.buffer.is_empty() && chunk_bytes > max_chunk_bytes {
+                self.flush_chunk()?;
+            }
+        }
+        self.value_len = self.value_len.max(value.len());
+        self.buffer.push((id, value));
+        if let Chunking::Values(values_per_chunk) = self.chunking {
+            if self.buffer.len() == values_per_chunk {
+                self.flush_chunk()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the remaining values. If no value was pushed at all, write an empty witness.
+    pub fn finish(mut self) -> Result<()> {
+        if !self.buffer.is_empty() || !self.written {
+            self.flush_chunk()?;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn flush_chunk(&mut self) -> Result<()> {
+        let witness = WitnessOwned {
+            assigned_variables: VariablesOwned::from(&self.buffer),
+        };
+        self.buffer.clear();
+        self.value_len = 0;
+        self.written = true;
+        witness.write_into(&mut self.writer)
+    }
+}
+
+
+#[test]
+fn test_witness_writer() {
+    use crate::MessageReader;
+    use crate::owned::message::MessageOwned;
+
+    let values: LinComb = (1..=5u64).map(|id| (id, vec![id as u8, 1])).collect();
+    let batch = WitnessOwned {
+        assigned_variables: VariablesOwned::from(&values),
+    };
+
+    let mut buf = Vec::<u8>::new();
+    let mut writer = WitnessWriter::new(&mut buf);
+    for (id, value) in &values {
+        writer.push(*id, value.clone()).unwrap();
+    }
+    writer.finish().unwrap();
+    let mut expected = Vec::<u8>::new();
+    batch.write_into(&mut expected).unwrap();
+    assert_eq!(buf, expected);
+
+    // Chunks of 2, 2, and 1 values.
+    let mut buf = Vec::<u8>::new();
+    let mut writer = WitnessWriter::with_values_per_chunk(&mut buf, 2);
+    for (id, value) in &values {
+        writer.push(*id, value.clone()).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let mut reader = MessageReader::new(&buf[..]);
+    let mut chunks = vec![];
+    while let Some(message) = reader.next_message().unwrap() {
+        match message {
+            MessageOwned::Witness(witness) => chunks.push(witness),
+            _ => panic!("unexpected message"),
+        }
+    }
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[1].assigned_variables.variable_ids, vec![3, 4]);
+    assert_eq!(chunks[2].assigned_variables.variable_ids, vec![5]);
+    let ids: Vec<u64> = chunks.iter().flat_map(|w| w.assigned_variables.variable_ids.clone()).collect();
+    let bytes: Vec<u8> = chunks.iter().flat_map(|w| w.assigned_variables.values.clone().unwrap()).collect();
+    assert_eq!(ids, batch.assigned_variables.variable_ids);
+    assert_eq!(Some(bytes), batch.assigned_variables.values);
+
+    // Nothing pushed.
+    let mut buf = Vec::<u8>::new();
+    WitnessWriter::with_values_per_chunk(&mut buf, 2).finish().unwrap();
+    let mut expected = Vec::<u8>::new();
+    WitnessOwned {
+        assigned_variables: VariablesOwned::from(&LinComb::new()),
+    }.write_into(&mut expected).unwrap();
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn test_witness_writer_max_chunk_bytes() {
+    use crate::MessageReader;
+    use crate::owned::message::MessageOwned;
+
+    // Wide values: 8 + 32 bytes per variable, so 3 variables per chunk of at most 128 bytes.
+    let values: LinComb = (1..=7u64).map(|id| (id, vec![id as u8; 32])).collect();
+    let mut buf = Vec::<u8>::new();
+    let mut writer = WitnessWriter::with_max_chunk_bytes(&mut buf, 128);
+    for (id, value) in &values {
+        writer.push(*id, value.clone()).unwrap();
+    }
+    // A single variable above the limit.
+    writer.push(8, vec![8; 200]).unwrap();
+    writer.push(9, vec![9]).unwrap();
+    writer.finish().unwrap();
+
+    let mut reader = MessageReader::new(&buf[..]);
+    let mut chunks = vec![];
+    while let Some(message) = reader.next_message().unwrap() {
+        match message {
+            MessageOwned::Witness(witness) => chunks.push(witness.assigned_variables.variable_ids),
+            _ => panic!("unexpected message"),
+        }
+    }
+    assert_eq!(chunks, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7], vec![8], vec![9]]);
+}
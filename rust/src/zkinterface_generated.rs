@@ -1,94 +1,124 @@
 // automatically generated by the FlatBuffers compiler, do not modify
 
 
+// @generated
 
-use std::mem;
-use std::cmp::Ordering;
+use core::mem;
+use core::cmp::Ordering;
 
 extern crate flatbuffers;
-use self::flatbuffers::EndianScalar;
+use self::flatbuffers::{EndianScalar, Follow};
 
 #[allow(unused_imports, dead_code)]
 pub mod zkinterface {
 
-  use std::mem;
-  use std::cmp::Ordering;
+  use core::mem;
+  use core::cmp::Ordering;
 
   extern crate flatbuffers;
-  use self::flatbuffers::EndianScalar;
-
-#[allow(non_camel_case_types)]
-#[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-pub enum Message {
-  NONE = 0,
-  Circuit = 1,
-  ConstraintSystem = 2,
-  Witness = 3,
-  Command = 4,
-
-}
+  use self::flatbuffers::{EndianScalar, Follow};
 
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_MESSAGE: u8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MAX_MESSAGE: u8 = 4;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_MESSAGE: [Message; 5] = [
+  Message::NONE,
+  Message::Circuit,
+  Message::ConstraintSystem,
+  Message::Witness,
+  Message::Command,
+];
 
-impl<'a> flatbuffers::Follow<'a> for Message {
-  type Inner = Self;
-  #[inline]
-  fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-    flatbuffers::read_scalar_at::<Self>(buf, loc)
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct Message(pub u8);
+#[allow(non_upper_case_globals)]
+impl Message {
+  pub const NONE: Self = Self(0);
+  pub const Circuit: Self = Self(1);
+  pub const ConstraintSystem: Self = Self(2);
+  pub const Witness: Self = Self(3);
+  pub const Command: Self = Self(4);
+
+  pub const ENUM_MIN: u8 = 0;
+  pub const ENUM_MAX: u8 = 4;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::NONE,
+    Self::Circuit,
+    Self::ConstraintSystem,
+    Self::Witness,
+    Self::Command,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::NONE => Some("NONE"),
+      Self::Circuit => Some("Circuit"),
+      Self::ConstraintSystem => Some("ConstraintSystem"),
+      Self::Witness => Some("Witness"),
+      Self::Command => Some("Command"),
+      _ => None,
+    }
   }
 }
-
-impl flatbuffers::EndianScalar for Message {
-  #[inline]
-  fn to_little_endian(self) -> Self {
-    let n = u8::to_le(self as u8);
-    let p = &n as *const u8 as *const Message;
-    unsafe { *p }
+impl core::fmt::Debug for Message {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
   }
+}
+impl<'a> flatbuffers::Follow<'a> for Message {
+  type Inner = Self;
   #[inline]
-  fn from_little_endian(self) -> Self {
-    let n = u8::from_le(self as u8);
-    let p = &n as *const u8 as *const Message;
-    unsafe { *p }
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<u8>(buf, loc);
+    Self(b)
   }
 }
 
 impl flatbuffers::Push for Message {
     type Output = Message;
     #[inline]
-    fn push(&self, dst: &mut [u8], _rest: &[u8]) {
-        flatbuffers::emplace_scalar::<Message>(dst, *self);
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<u8>(dst, self.0);
     }
 }
 
-#[allow(non_camel_case_types)]
-pub const ENUM_VALUES_MESSAGE:[Message; 5] = [
-  Message::NONE,
-  Message::Circuit,
-  Message::ConstraintSystem,
-  Message::Witness,
-  Message::Command
-];
-
-#[allow(non_camel_case_types)]
-pub const ENUM_NAMES_MESSAGE:[&'static str; 5] = [
-    "NONE",
-    "Circuit",
-    "ConstraintSystem",
-    "Witness",
-    "Command"
-];
+impl flatbuffers::EndianScalar for Message {
+  type Scalar = u8;
+  #[inline]
+  fn to_little_endian(self) -> u8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: u8) -> Self {
+    let b = u8::from_le(v);
+    Self(b)
+  }
+}
 
-pub fn enum_name_message(e: Message) -> &'static str {
-  let index = e as u8;
-  ENUM_NAMES_MESSAGE[index as usize]
+impl<'a> flatbuffers::Verifiable for Message {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    u8::run_verifier(v, pos)
+  }
 }
 
+impl flatbuffers::SimpleToVerifyInSlice for Message {}
 pub struct MessageUnionTableOffset {}
+
 pub enum CircuitOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// A description of a circuit or sub-circuit.
 /// This can be a complete circuit ready for proving,
@@ -98,38 +128,36 @@ pub struct Circuit<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for Circuit<'a> {
-    type Inner = Circuit<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = Circuit<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> Circuit<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        Circuit {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args CircuitArgs<'args>) -> flatbuffers::WIPOffset<Circuit<'bldr>> {
-      let mut builder = CircuitBuilder::new(_fbb);
-      builder.add_free_variable_id(args.free_variable_id);
-      if let Some(x) = args.configuration { builder.add_configuration(x); }
-      if let Some(x) = args.field_maximum { builder.add_field_maximum(x); }
-      if let Some(x) = args.connections { builder.add_connections(x); }
-      builder.finish()
-    }
+  pub const VT_CONNECTIONS: flatbuffers::VOffsetT = 4;
+  pub const VT_FREE_VARIABLE_ID: flatbuffers::VOffsetT = 6;
+  pub const VT_FIELD_MAXIMUM: flatbuffers::VOffsetT = 8;
+  pub const VT_CONFIGURATION: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Circuit { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args CircuitArgs<'args>
+  ) -> flatbuffers::WIPOffset<Circuit<'bldr>> {
+    let mut builder = CircuitBuilder::new(_fbb);
+    builder.add_free_variable_id(args.free_variable_id);
+    if let Some(x) = args.configuration { builder.add_configuration(x); }
+    if let Some(x) = args.field_maximum { builder.add_field_maximum(x); }
+    if let Some(x) = args.connections { builder.add_connections(x); }
+    builder.finish()
+  }
 
-    pub const VT_CONNECTIONS: flatbuffers::VOffsetT = 4;
-    pub const VT_FREE_VARIABLE_ID: flatbuffers::VOffsetT = 6;
-    pub const VT_FIELD_MAXIMUM: flatbuffers::VOffsetT = 8;
-    pub const VT_CONFIGURATION: flatbuffers::VOffsetT = 10;
 
   /// Variables to use as connections to the sub-circuit.
   ///
@@ -140,20 +168,29 @@ impl<'a> Circuit<'a> {
   /// - If using `Command.witness_generation`, variables must be assigned values.
   #[inline]
   pub fn connections(&self) -> Option<Variables<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<Variables<'a>>>(Circuit::VT_CONNECTIONS, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Variables>>(Circuit::VT_CONNECTIONS, None)}
   }
   /// A variable ID greater than all IDs allocated by the sender of this message.
   /// The recipient of this message can allocate new IDs >= free_variable_id.
   #[inline]
   pub fn free_variable_id(&self) -> u64 {
-    self._tab.get::<u64>(Circuit::VT_FREE_VARIABLE_ID, Some(0)).unwrap()
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Circuit::VT_FREE_VARIABLE_ID, Some(0)).unwrap()}
   }
   /// The largest element of the finite field used by the current system.
   /// A canonical little-endian representation of the field order minus one.
   /// See `Variables.values` below.
   #[inline]
-  pub fn field_maximum(&self) -> Option<&'a [u8]> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Circuit::VT_FIELD_MAXIMUM, None).map(|v| v.safe_slice())
+  pub fn field_maximum(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Circuit::VT_FIELD_MAXIMUM, None)}
   }
   /// Optional: Any custom parameter that may influence the circuit construction.
   ///
@@ -162,27 +199,46 @@ impl<'a> Circuit<'a> {
   /// Counter-example: a Merkle path is not config and belongs in `connections.info`.
   #[inline]
   pub fn configuration(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>(Circuit::VT_CONFIGURATION, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue>>>>(Circuit::VT_CONFIGURATION, None)}
   }
 }
 
+impl flatbuffers::Verifiable for Circuit<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Variables>>("connections", Self::VT_CONNECTIONS, false)?
+     .visit_field::<u64>("free_variable_id", Self::VT_FREE_VARIABLE_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("field_maximum", Self::VT_FIELD_MAXIMUM, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<KeyValue>>>>("configuration", Self::VT_CONFIGURATION, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct CircuitArgs<'a> {
-    pub connections: Option<flatbuffers::WIPOffset<Variables<'a >>>,
+    pub connections: Option<flatbuffers::WIPOffset<Variables<'a>>>,
     pub free_variable_id: u64,
-    pub field_maximum: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
-    pub configuration: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<KeyValue<'a >>>>>,
+    pub field_maximum: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub configuration: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>,
 }
 impl<'a> Default for CircuitArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        CircuitArgs {
-            connections: None,
-            free_variable_id: 0,
-            field_maximum: None,
-            configuration: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    CircuitArgs {
+      connections: None,
+      free_variable_id: 0,
+      field_maximum: None,
+      configuration: None,
     }
+  }
 }
+
 pub struct CircuitBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -219,8 +275,18 @@ impl<'a: 'b, 'b> CircuitBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for Circuit<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Circuit");
+      ds.field("connections", &self.connections());
+      ds.field("free_variable_id", &self.free_variable_id());
+      ds.field("field_maximum", &self.field_maximum());
+      ds.field("configuration", &self.configuration());
+      ds.finish()
+  }
+}
 pub enum ConstraintSystemOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// ConstraintSystem represents constraints to be added to the constraint system.
 ///
@@ -230,38 +296,39 @@ pub struct ConstraintSystem<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for ConstraintSystem<'a> {
-    type Inner = ConstraintSystem<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = ConstraintSystem<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> ConstraintSystem<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        ConstraintSystem {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args ConstraintSystemArgs<'args>) -> flatbuffers::WIPOffset<ConstraintSystem<'bldr>> {
-      let mut builder = ConstraintSystemBuilder::new(_fbb);
-      if let Some(x) = args.info { builder.add_info(x); }
-      if let Some(x) = args.constraints { builder.add_constraints(x); }
-      builder.finish()
-    }
+  pub const VT_CONSTRAINTS: flatbuffers::VOffsetT = 4;
+  pub const VT_INFO: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ConstraintSystem { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args ConstraintSystemArgs<'args>
+  ) -> flatbuffers::WIPOffset<ConstraintSystem<'bldr>> {
+    let mut builder = ConstraintSystemBuilder::new(_fbb);
+    if let Some(x) = args.info { builder.add_info(x); }
+    if let Some(x) = args.constraints { builder.add_constraints(x); }
+    builder.finish()
+  }
 
-    pub const VT_CONSTRAINTS: flatbuffers::VOffsetT = 4;
-    pub const VT_INFO: flatbuffers::VOffsetT = 6;
 
   #[inline]
   pub fn constraints(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<BilinearConstraint<'a>>>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<BilinearConstraint<'a>>>>>(ConstraintSystem::VT_CONSTRAINTS, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<BilinearConstraint>>>>(ConstraintSystem::VT_CONSTRAINTS, None)}
   }
   /// Optional: Any complementary info that may be useful.
   ///
@@ -269,23 +336,40 @@ impl<'a> ConstraintSystem<'a> {
   /// Example: custom hints to an optimizer or analyzer.
   #[inline]
   pub fn info(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>(ConstraintSystem::VT_INFO, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue>>>>(ConstraintSystem::VT_INFO, None)}
   }
 }
 
+impl flatbuffers::Verifiable for ConstraintSystem<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<BilinearConstraint>>>>("constraints", Self::VT_CONSTRAINTS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<KeyValue>>>>("info", Self::VT_INFO, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct ConstraintSystemArgs<'a> {
-    pub constraints: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<BilinearConstraint<'a >>>>>,
-    pub info: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<KeyValue<'a >>>>>,
+    pub constraints: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<BilinearConstraint<'a>>>>>,
+    pub info: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>,
 }
 impl<'a> Default for ConstraintSystemArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        ConstraintSystemArgs {
-            constraints: None,
-            info: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    ConstraintSystemArgs {
+      constraints: None,
+      info: None,
     }
+  }
 }
+
 pub struct ConstraintSystemBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -314,8 +398,16 @@ impl<'a: 'b, 'b> ConstraintSystemBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for ConstraintSystem<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ConstraintSystem");
+      ds.field("constraints", &self.constraints());
+      ds.field("info", &self.info());
+      ds.finish()
+  }
+}
 pub enum WitnessOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// Witness represents an assignment of values to variables.
 ///
@@ -327,50 +419,64 @@ pub struct Witness<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for Witness<'a> {
-    type Inner = Witness<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = Witness<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> Witness<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        Witness {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args WitnessArgs<'args>) -> flatbuffers::WIPOffset<Witness<'bldr>> {
-      let mut builder = WitnessBuilder::new(_fbb);
-      if let Some(x) = args.assigned_variables { builder.add_assigned_variables(x); }
-      builder.finish()
-    }
+  pub const VT_ASSIGNED_VARIABLES: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Witness { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args WitnessArgs<'args>
+  ) -> flatbuffers::WIPOffset<Witness<'bldr>> {
+    let mut builder = WitnessBuilder::new(_fbb);
+    if let Some(x) = args.assigned_variables { builder.add_assigned_variables(x); }
+    builder.finish()
+  }
 
-    pub const VT_ASSIGNED_VARIABLES: flatbuffers::VOffsetT = 4;
 
   #[inline]
   pub fn assigned_variables(&self) -> Option<Variables<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<Variables<'a>>>(Witness::VT_ASSIGNED_VARIABLES, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Variables>>(Witness::VT_ASSIGNED_VARIABLES, None)}
   }
 }
 
+impl flatbuffers::Verifiable for Witness<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Variables>>("assigned_variables", Self::VT_ASSIGNED_VARIABLES, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct WitnessArgs<'a> {
-    pub assigned_variables: Option<flatbuffers::WIPOffset<Variables<'a >>>,
+    pub assigned_variables: Option<flatbuffers::WIPOffset<Variables<'a>>>,
 }
 impl<'a> Default for WitnessArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        WitnessArgs {
-            assigned_variables: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    WitnessArgs {
+      assigned_variables: None,
     }
+  }
 }
+
 pub struct WitnessBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -395,8 +501,15 @@ impl<'a: 'b, 'b> WitnessBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for Witness<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Witness");
+      ds.field("assigned_variables", &self.assigned_variables());
+      ds.finish()
+  }
+}
 pub enum CommandOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// Optional: Command messages can be used to request actions from the receiver. This makes it
 /// possible to write code that works in different environments. Commands and parameters
@@ -407,36 +520,34 @@ pub struct Command<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for Command<'a> {
-    type Inner = Command<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = Command<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> Command<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        Command {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args CommandArgs<'args>) -> flatbuffers::WIPOffset<Command<'bldr>> {
-      let mut builder = CommandBuilder::new(_fbb);
-      if let Some(x) = args.parameters { builder.add_parameters(x); }
-      builder.add_witness_generation(args.witness_generation);
-      builder.add_constraints_generation(args.constraints_generation);
-      builder.finish()
-    }
+  pub const VT_CONSTRAINTS_GENERATION: flatbuffers::VOffsetT = 4;
+  pub const VT_WITNESS_GENERATION: flatbuffers::VOffsetT = 6;
+  pub const VT_PARAMETERS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Command { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args CommandArgs<'args>
+  ) -> flatbuffers::WIPOffset<Command<'bldr>> {
+    let mut builder = CommandBuilder::new(_fbb);
+    if let Some(x) = args.parameters { builder.add_parameters(x); }
+    builder.add_witness_generation(args.witness_generation);
+    builder.add_constraints_generation(args.constraints_generation);
+    builder.finish()
+  }
 
-    pub const VT_CONSTRAINTS_GENERATION: flatbuffers::VOffsetT = 4;
-    pub const VT_WITNESS_GENERATION: flatbuffers::VOffsetT = 6;
-    pub const VT_PARAMETERS: flatbuffers::VOffsetT = 8;
 
   /// For gadget flows.
   /// Request the generation of a constraint system (or part thereof).
@@ -445,7 +556,10 @@ impl<'a> Command<'a> {
   /// followed by one or more ConstraintSystem messages.
   #[inline]
   pub fn constraints_generation(&self) -> bool {
-    self._tab.get::<bool>(Command::VT_CONSTRAINTS_GENERATION, Some(false)).unwrap()
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(Command::VT_CONSTRAINTS_GENERATION, Some(false)).unwrap()}
   }
   /// For gadget flows.
   /// Request the generation of a witness (or part thereof).
@@ -455,30 +569,51 @@ impl<'a> Command<'a> {
   /// with output values in `connections` variables, followed by one or more `Witness` messages.
   #[inline]
   pub fn witness_generation(&self) -> bool {
-    self._tab.get::<bool>(Command::VT_WITNESS_GENERATION, Some(false)).unwrap()
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(Command::VT_WITNESS_GENERATION, Some(false)).unwrap()}
   }
   /// Optional: Any complementary parameter that may be useful.
   #[inline]
   pub fn parameters(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>(Command::VT_PARAMETERS, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue>>>>(Command::VT_PARAMETERS, None)}
   }
 }
 
+impl flatbuffers::Verifiable for Command<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<bool>("constraints_generation", Self::VT_CONSTRAINTS_GENERATION, false)?
+     .visit_field::<bool>("witness_generation", Self::VT_WITNESS_GENERATION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<KeyValue>>>>("parameters", Self::VT_PARAMETERS, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct CommandArgs<'a> {
     pub constraints_generation: bool,
     pub witness_generation: bool,
-    pub parameters: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<KeyValue<'a >>>>>,
+    pub parameters: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>,
 }
 impl<'a> Default for CommandArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        CommandArgs {
-            constraints_generation: false,
-            witness_generation: false,
-            parameters: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    CommandArgs {
+      constraints_generation: false,
+      witness_generation: false,
+      parameters: None,
     }
+  }
 }
+
 pub struct CommandBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -511,8 +646,17 @@ impl<'a: 'b, 'b> CommandBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for Command<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Command");
+      ds.field("constraints_generation", &self.constraints_generation());
+      ds.field("witness_generation", &self.witness_generation());
+      ds.field("parameters", &self.parameters());
+      ds.finish()
+  }
+}
 pub enum BilinearConstraintOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// A single R1CS constraint between variables.
 ///
@@ -524,66 +668,88 @@ pub struct BilinearConstraint<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for BilinearConstraint<'a> {
-    type Inner = BilinearConstraint<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = BilinearConstraint<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> BilinearConstraint<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        BilinearConstraint {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args BilinearConstraintArgs<'args>) -> flatbuffers::WIPOffset<BilinearConstraint<'bldr>> {
-      let mut builder = BilinearConstraintBuilder::new(_fbb);
-      if let Some(x) = args.linear_combination_c { builder.add_linear_combination_c(x); }
-      if let Some(x) = args.linear_combination_b { builder.add_linear_combination_b(x); }
-      if let Some(x) = args.linear_combination_a { builder.add_linear_combination_a(x); }
-      builder.finish()
-    }
+  pub const VT_LINEAR_COMBINATION_A: flatbuffers::VOffsetT = 4;
+  pub const VT_LINEAR_COMBINATION_B: flatbuffers::VOffsetT = 6;
+  pub const VT_LINEAR_COMBINATION_C: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    BilinearConstraint { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args BilinearConstraintArgs<'args>
+  ) -> flatbuffers::WIPOffset<BilinearConstraint<'bldr>> {
+    let mut builder = BilinearConstraintBuilder::new(_fbb);
+    if let Some(x) = args.linear_combination_c { builder.add_linear_combination_c(x); }
+    if let Some(x) = args.linear_combination_b { builder.add_linear_combination_b(x); }
+    if let Some(x) = args.linear_combination_a { builder.add_linear_combination_a(x); }
+    builder.finish()
+  }
 
-    pub const VT_LINEAR_COMBINATION_A: flatbuffers::VOffsetT = 4;
-    pub const VT_LINEAR_COMBINATION_B: flatbuffers::VOffsetT = 6;
-    pub const VT_LINEAR_COMBINATION_C: flatbuffers::VOffsetT = 8;
 
   #[inline]
   pub fn linear_combination_a(&self) -> Option<Variables<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<Variables<'a>>>(BilinearConstraint::VT_LINEAR_COMBINATION_A, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Variables>>(BilinearConstraint::VT_LINEAR_COMBINATION_A, None)}
   }
   #[inline]
   pub fn linear_combination_b(&self) -> Option<Variables<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<Variables<'a>>>(BilinearConstraint::VT_LINEAR_COMBINATION_B, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Variables>>(BilinearConstraint::VT_LINEAR_COMBINATION_B, None)}
   }
   #[inline]
   pub fn linear_combination_c(&self) -> Option<Variables<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<Variables<'a>>>(BilinearConstraint::VT_LINEAR_COMBINATION_C, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Variables>>(BilinearConstraint::VT_LINEAR_COMBINATION_C, None)}
   }
 }
 
+impl flatbuffers::Verifiable for BilinearConstraint<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Variables>>("linear_combination_a", Self::VT_LINEAR_COMBINATION_A, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Variables>>("linear_combination_b", Self::VT_LINEAR_COMBINATION_B, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Variables>>("linear_combination_c", Self::VT_LINEAR_COMBINATION_C, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct BilinearConstraintArgs<'a> {
-    pub linear_combination_a: Option<flatbuffers::WIPOffset<Variables<'a >>>,
-    pub linear_combination_b: Option<flatbuffers::WIPOffset<Variables<'a >>>,
-    pub linear_combination_c: Option<flatbuffers::WIPOffset<Variables<'a >>>,
+    pub linear_combination_a: Option<flatbuffers::WIPOffset<Variables<'a>>>,
+    pub linear_combination_b: Option<flatbuffers::WIPOffset<Variables<'a>>>,
+    pub linear_combination_c: Option<flatbuffers::WIPOffset<Variables<'a>>>,
 }
 impl<'a> Default for BilinearConstraintArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        BilinearConstraintArgs {
-            linear_combination_a: None,
-            linear_combination_b: None,
-            linear_combination_c: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    BilinearConstraintArgs {
+      linear_combination_a: None,
+      linear_combination_b: None,
+      linear_combination_c: None,
     }
+  }
 }
+
 pub struct BilinearConstraintBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -616,8 +782,17 @@ impl<'a: 'b, 'b> BilinearConstraintBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for BilinearConstraint<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("BilinearConstraint");
+      ds.field("linear_combination_a", &self.linear_combination_a());
+      ds.field("linear_combination_b", &self.linear_combination_b());
+      ds.field("linear_combination_c", &self.linear_combination_c());
+      ds.finish()
+  }
+}
 pub enum VariablesOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// A description of multiple variables.
 ///
@@ -633,36 +808,34 @@ pub struct Variables<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for Variables<'a> {
-    type Inner = Variables<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = Variables<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> Variables<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        Variables {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args VariablesArgs<'args>) -> flatbuffers::WIPOffset<Variables<'bldr>> {
-      let mut builder = VariablesBuilder::new(_fbb);
-      if let Some(x) = args.info { builder.add_info(x); }
-      if let Some(x) = args.values { builder.add_values(x); }
-      if let Some(x) = args.variable_ids { builder.add_variable_ids(x); }
-      builder.finish()
-    }
+  pub const VT_VARIABLE_IDS: flatbuffers::VOffsetT = 4;
+  pub const VT_VALUES: flatbuffers::VOffsetT = 6;
+  pub const VT_INFO: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Variables { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args VariablesArgs<'args>
+  ) -> flatbuffers::WIPOffset<Variables<'bldr>> {
+    let mut builder = VariablesBuilder::new(_fbb);
+    if let Some(x) = args.info { builder.add_info(x); }
+    if let Some(x) = args.values { builder.add_values(x); }
+    if let Some(x) = args.variable_ids { builder.add_variable_ids(x); }
+    builder.finish()
+  }
 
-    pub const VT_VARIABLE_IDS: flatbuffers::VOffsetT = 4;
-    pub const VT_VALUES: flatbuffers::VOffsetT = 6;
-    pub const VT_INFO: flatbuffers::VOffsetT = 8;
 
   /// The IDs of the variables.
   ///
@@ -670,7 +843,10 @@ impl<'a> Variables<'a> {
   /// - The ID 0 always represents the constant variable one.
   #[inline]
   pub fn variable_ids(&self) -> Option<flatbuffers::Vector<'a, u64>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u64>>>(Variables::VT_VARIABLE_IDS, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u64>>>(Variables::VT_VARIABLE_IDS, None)}
   }
   /// Optional: values assigned to variables.
   ///
@@ -684,8 +860,11 @@ impl<'a> Variables<'a> {
   ///
   ///     element size = values.length / variable_ids.length
   #[inline]
-  pub fn values(&self) -> Option<&'a [u8]> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Variables::VT_VALUES, None).map(|v| v.safe_slice())
+  pub fn values(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(Variables::VT_VALUES, None)}
   }
   /// Optional: Any complementary info that may be useful to the recipient.
   ///
@@ -694,25 +873,43 @@ impl<'a> Variables<'a> {
   /// Example: a Merkle authentication path in some custom format.
   #[inline]
   pub fn info(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>(Variables::VT_INFO, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue>>>>(Variables::VT_INFO, None)}
   }
 }
 
+impl flatbuffers::Verifiable for Variables<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u64>>>("variable_ids", Self::VT_VARIABLE_IDS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("values", Self::VT_VALUES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<KeyValue>>>>("info", Self::VT_INFO, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct VariablesArgs<'a> {
-    pub variable_ids: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u64>>>,
-    pub values: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
-    pub info: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a , flatbuffers::ForwardsUOffset<KeyValue<'a >>>>>,
+    pub variable_ids: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u64>>>,
+    pub values: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub info: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<KeyValue<'a>>>>>,
 }
 impl<'a> Default for VariablesArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        VariablesArgs {
-            variable_ids: None,
-            values: None,
-            info: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    VariablesArgs {
+      variable_ids: None,
+      values: None,
+      info: None,
     }
+  }
 }
+
 pub struct VariablesBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -745,8 +942,17 @@ impl<'a: 'b, 'b> VariablesBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for Variables<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Variables");
+      ds.field("variable_ids", &self.variable_ids());
+      ds.field("values", &self.values());
+      ds.field("info", &self.info());
+      ds.finish()
+  }
+}
 pub enum KeyValueOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 /// Generic key-value for custom attributes.
 /// The key must be a string.
@@ -756,74 +962,100 @@ pub struct KeyValue<'a> {
 }
 
 impl<'a> flatbuffers::Follow<'a> for KeyValue<'a> {
-    type Inner = KeyValue<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = KeyValue<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> KeyValue<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        KeyValue {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args KeyValueArgs<'args>) -> flatbuffers::WIPOffset<KeyValue<'bldr>> {
-      let mut builder = KeyValueBuilder::new(_fbb);
-      builder.add_number(args.number);
-      if let Some(x) = args.text { builder.add_text(x); }
-      if let Some(x) = args.data { builder.add_data(x); }
-      if let Some(x) = args.key { builder.add_key(x); }
-      builder.finish()
-    }
+  pub const VT_KEY: flatbuffers::VOffsetT = 4;
+  pub const VT_DATA: flatbuffers::VOffsetT = 6;
+  pub const VT_TEXT: flatbuffers::VOffsetT = 8;
+  pub const VT_NUMBER: flatbuffers::VOffsetT = 10;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    KeyValue { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args KeyValueArgs<'args>
+  ) -> flatbuffers::WIPOffset<KeyValue<'bldr>> {
+    let mut builder = KeyValueBuilder::new(_fbb);
+    builder.add_number(args.number);
+    if let Some(x) = args.text { builder.add_text(x); }
+    if let Some(x) = args.data { builder.add_data(x); }
+    if let Some(x) = args.key { builder.add_key(x); }
+    builder.finish()
+  }
 
-    pub const VT_KEY: flatbuffers::VOffsetT = 4;
-    pub const VT_DATA: flatbuffers::VOffsetT = 6;
-    pub const VT_TEXT: flatbuffers::VOffsetT = 8;
-    pub const VT_NUMBER: flatbuffers::VOffsetT = 10;
 
   #[inline]
   pub fn key(&self) -> Option<&'a str> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyValue::VT_KEY, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyValue::VT_KEY, None)}
   }
   #[inline]
-  pub fn data(&self) -> Option<&'a [u8]> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(KeyValue::VT_DATA, None).map(|v| v.safe_slice())
+  pub fn data(&self) -> Option<flatbuffers::Vector<'a, u8>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(KeyValue::VT_DATA, None)}
   }
   #[inline]
   pub fn text(&self) -> Option<&'a str> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyValue::VT_TEXT, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(KeyValue::VT_TEXT, None)}
   }
   #[inline]
   pub fn number(&self) -> i64 {
-    self._tab.get::<i64>(KeyValue::VT_NUMBER, Some(0)).unwrap()
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i64>(KeyValue::VT_NUMBER, Some(0)).unwrap()}
   }
 }
 
+impl flatbuffers::Verifiable for KeyValue<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("key", Self::VT_KEY, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("data", Self::VT_DATA, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("text", Self::VT_TEXT, false)?
+     .visit_field::<i64>("number", Self::VT_NUMBER, false)?
+     .finish();
+    Ok(())
+  }
+}
 pub struct KeyValueArgs<'a> {
-    pub key: Option<flatbuffers::WIPOffset<&'a  str>>,
-    pub data: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a ,  u8>>>,
-    pub text: Option<flatbuffers::WIPOffset<&'a  str>>,
+    pub key: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub data: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub text: Option<flatbuffers::WIPOffset<&'a str>>,
     pub number: i64,
 }
 impl<'a> Default for KeyValueArgs<'a> {
-    #[inline]
-    fn default() -> Self {
-        KeyValueArgs {
-            key: None,
-            data: None,
-            text: None,
-            number: 0,
-        }
+  #[inline]
+  fn default() -> Self {
+    KeyValueArgs {
+      key: None,
+      data: None,
+      text: None,
+      number: 0,
     }
+  }
 }
+
 pub struct KeyValueBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -860,56 +1092,75 @@ impl<'a: 'b, 'b> KeyValueBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for KeyValue<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("KeyValue");
+      ds.field("key", &self.key());
+      ds.field("data", &self.data());
+      ds.field("text", &self.text());
+      ds.field("number", &self.number());
+      ds.finish()
+  }
+}
 pub enum RootOffset {}
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, PartialEq)]
 
 pub struct Root<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
 impl<'a> flatbuffers::Follow<'a> for Root<'a> {
-    type Inner = Root<'a>;
-    #[inline]
-    fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
-        Self {
-            _tab: flatbuffers::Table { buf: buf, loc: loc },
-        }
-    }
+  type Inner = Root<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
 }
 
 impl<'a> Root<'a> {
-    #[inline]
-    pub fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-        Root {
-            _tab: table,
-        }
-    }
-    #[allow(unused_mut)]
-    pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
-        _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
-        args: &'args RootArgs) -> flatbuffers::WIPOffset<Root<'bldr>> {
-      let mut builder = RootBuilder::new(_fbb);
-      if let Some(x) = args.message { builder.add_message(x); }
-      builder.add_message_type(args.message_type);
-      builder.finish()
-    }
+  pub const VT_MESSAGE_TYPE: flatbuffers::VOffsetT = 4;
+  pub const VT_MESSAGE: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Root { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr>,
+    args: &'args RootArgs
+  ) -> flatbuffers::WIPOffset<Root<'bldr>> {
+    let mut builder = RootBuilder::new(_fbb);
+    if let Some(x) = args.message { builder.add_message(x); }
+    builder.add_message_type(args.message_type);
+    builder.finish()
+  }
 
-    pub const VT_MESSAGE_TYPE: flatbuffers::VOffsetT = 4;
-    pub const VT_MESSAGE: flatbuffers::VOffsetT = 6;
 
   #[inline]
   pub fn message_type(&self) -> Message {
-    self._tab.get::<Message>(Root::VT_MESSAGE_TYPE, Some(Message::NONE)).unwrap()
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<Message>(Root::VT_MESSAGE_TYPE, Some(Message::NONE)).unwrap()}
   }
   #[inline]
   pub fn message(&self) -> Option<flatbuffers::Table<'a>> {
-    self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(Root::VT_MESSAGE, None)
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Table<'a>>>(Root::VT_MESSAGE, None)}
   }
   #[inline]
   #[allow(non_snake_case)]
   pub fn message_as_circuit(&self) -> Option<Circuit<'a>> {
     if self.message_type() == Message::Circuit {
-      self.message().map(|u| Circuit::init_from_table(u))
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Circuit::init_from_table(t) }
+     })
     } else {
       None
     }
@@ -919,7 +1170,12 @@ impl<'a> Root<'a> {
   #[allow(non_snake_case)]
   pub fn message_as_constraint_system(&self) -> Option<ConstraintSystem<'a>> {
     if self.message_type() == Message::ConstraintSystem {
-      self.message().map(|u| ConstraintSystem::init_from_table(u))
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { ConstraintSystem::init_from_table(t) }
+     })
     } else {
       None
     }
@@ -929,7 +1185,12 @@ impl<'a> Root<'a> {
   #[allow(non_snake_case)]
   pub fn message_as_witness(&self) -> Option<Witness<'a>> {
     if self.message_type() == Message::Witness {
-      self.message().map(|u| Witness::init_from_table(u))
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Witness::init_from_table(t) }
+     })
     } else {
       None
     }
@@ -939,7 +1200,12 @@ impl<'a> Root<'a> {
   #[allow(non_snake_case)]
   pub fn message_as_command(&self) -> Option<Command<'a>> {
     if self.message_type() == Message::Command {
-      self.message().map(|u| Command::init_from_table(u))
+      self.message().map(|t| {
+       // Safety:
+       // Created from a valid Table for this object
+       // Which contains a valid union in this slot
+       unsafe { Command::init_from_table(t) }
+     })
     } else {
       None
     }
@@ -947,19 +1213,40 @@ impl<'a> Root<'a> {
 
 }
 
+impl flatbuffers::Verifiable for Root<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_union::<Message, _>("message_type", Self::VT_MESSAGE_TYPE, "message", Self::VT_MESSAGE, false, |key, v, pos| {
+        match key {
+          Message::Circuit => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Circuit>>("Message::Circuit", pos),
+          Message::ConstraintSystem => v.verify_union_variant::<flatbuffers::ForwardsUOffset<ConstraintSystem>>("Message::ConstraintSystem", pos),
+          Message::Witness => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Witness>>("Message::Witness", pos),
+          Message::Command => v.verify_union_variant::<flatbuffers::ForwardsUOffset<Command>>("Message::Command", pos),
+          _ => Ok(()),
+        }
+     })?
+     .finish();
+    Ok(())
+  }
+}
 pub struct RootArgs {
     pub message_type: Message,
     pub message: Option<flatbuffers::WIPOffset<flatbuffers::UnionWIPOffset>>,
 }
 impl<'a> Default for RootArgs {
-    #[inline]
-    fn default() -> Self {
-        RootArgs {
-            message_type: Message::NONE,
-            message: None,
-        }
+  #[inline]
+  fn default() -> Self {
+    RootArgs {
+      message_type: Message::NONE,
+      message: None,
     }
+  }
 }
+
 pub struct RootBuilder<'a: 'b, 'b> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
@@ -988,29 +1275,120 @@ impl<'a: 'b, 'b> RootBuilder<'a, 'b> {
   }
 }
 
+impl core::fmt::Debug for Root<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Root");
+      ds.field("message_type", &self.message_type());
+      match self.message_type() {
+        Message::Circuit => {
+          if let Some(x) = self.message_as_circuit() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        Message::ConstraintSystem => {
+          if let Some(x) = self.message_as_constraint_system() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        Message::Witness => {
+          if let Some(x) = self.message_as_witness() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        Message::Command => {
+          if let Some(x) = self.message_as_command() {
+            ds.field("message", &x)
+          } else {
+            ds.field("message", &"InvalidFlatbuffer: Union discriminant does not match value.")
+          }
+        },
+        _ => {
+          let x: Option<()> = None;
+          ds.field("message", &x)
+        },
+      };
+      ds.finish()
+  }
+}
 #[inline]
-pub fn get_root_as_root<'a>(buf: &'a [u8]) -> Root<'a> {
-  flatbuffers::get_root::<Root<'a>>(buf)
+/// Verifies that a buffer of bytes contains a `Root`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_root_unchecked`.
+pub fn root_as_root(buf: &[u8]) -> Result<Root, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<Root>(buf)
 }
-
 #[inline]
-pub fn get_size_prefixed_root_as_root<'a>(buf: &'a [u8]) -> Root<'a> {
-  flatbuffers::get_size_prefixed_root::<Root<'a>>(buf)
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `Root` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_root_unchecked`.
+pub fn size_prefixed_root_as_root(buf: &[u8]) -> Result<Root, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<Root>(buf)
 }
-
-pub const ROOT_IDENTIFIER: &'static str = "zkif";
+#[inline]
+/// Verifies, with the given options, that a buffer of bytes
+/// contains a `Root` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_root_unchecked`.
+pub fn root_as_root_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<Root<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root_with_opts::<Root<'b>>(opts, buf)
+}
+#[inline]
+/// Verifies, with the given verifier options, that a buffer of
+/// bytes contains a size prefixed `Root` and returns
+/// it. Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_root_unchecked`.
+pub fn size_prefixed_root_as_root_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<Root<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root_with_opts::<Root<'b>>(opts, buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a Root and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `Root`.
+pub unsafe fn root_as_root_unchecked(buf: &[u8]) -> Root {
+  flatbuffers::root_unchecked::<Root>(buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a size prefixed Root and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid size prefixed `Root`.
+pub unsafe fn size_prefixed_root_as_root_unchecked(buf: &[u8]) -> Root {
+  flatbuffers::size_prefixed_root_unchecked::<Root>(buf)
+}
+pub const ROOT_IDENTIFIER: &str = "zkif";
 
 #[inline]
 pub fn root_buffer_has_identifier(buf: &[u8]) -> bool {
-  return flatbuffers::buffer_has_identifier(buf, ROOT_IDENTIFIER, false);
+  flatbuffers::buffer_has_identifier(buf, ROOT_IDENTIFIER, false)
 }
 
 #[inline]
 pub fn root_size_prefixed_buffer_has_identifier(buf: &[u8]) -> bool {
-  return flatbuffers::buffer_has_identifier(buf, ROOT_IDENTIFIER, true);
+  flatbuffers::buffer_has_identifier(buf, ROOT_IDENTIFIER, true)
 }
 
-pub const ROOT_EXTENSION: &'static str = "zkif";
+pub const ROOT_EXTENSION: &str = "zkif";
 
 #[inline]
 pub fn finish_root_buffer<'a, 'b>(